@@ -0,0 +1,351 @@
+use anchor_lang::{system_program, AccountDeserialize, Discriminator};
+use anchor_spl::associated_token::get_associated_token_address;
+use shadow_protocol::state::*;
+use shadow_protocol::{accounts, instruction};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::error::{ClientError, Result};
+use crate::{instructions, pda};
+
+/// Decode an Anchor account, checking its discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    Ok(T::try_deserialize(&mut &data[..])?)
+}
+
+/// Async client for the common auction flows. Anything not covered here can
+/// be built with [`crate::instructions`] and sent through [`ShadowClient::send`].
+pub struct ShadowClient {
+    rpc: RpcClient,
+    payer: Keypair,
+}
+
+impl ShadowClient {
+    /// Connect to `rpc_url` at confirmed commitment
+    pub fn new(rpc_url: impl Into<String>, payer: Keypair) -> Self {
+        Self::with_rpc(
+            RpcClient::new_with_commitment(rpc_url.into(), CommitmentConfig::confirmed()),
+            payer,
+        )
+    }
+    
+    pub fn with_rpc(rpc: RpcClient, payer: Keypair) -> Self {
+        Self { rpc, payer }
+    }
+    
+    pub fn rpc(&self) -> &RpcClient {
+        &self.rpc
+    }
+    
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+    
+    /// Sign with the payer (plus `signers`) and send `ixs` as one transaction
+    pub async fn send(&self, ixs: &[Instruction], signers: &[&Keypair]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let mut all_signers = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.payer()), &all_signers, blockhash);
+        Ok(self.rpc.send_and_confirm_transaction(&tx).await?)
+    }
+    
+    /// Fetch and decode a program account
+    pub async fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())
+            .await?
+            .value
+            .ok_or(ClientError::AccountNotFound(*address))?;
+        decode(&account.data)
+    }
+    
+    pub async fn protocol_state(&self) -> Result<ProtocolState> {
+        self.fetch(&pda::protocol()).await
+    }
+    
+    pub async fn auction(&self, auction_id: u64) -> Result<AuctionAccount> {
+        self.fetch(&pda::auction(auction_id)).await
+    }
+    
+    pub async fn bid(&self, auction_id: u64, bidder: &Pubkey) -> Result<BidAccount> {
+        self.fetch(&pda::bid(auction_id, bidder)).await
+    }
+    
+    pub async fn batch(&self, batch_id: u64) -> Result<BatchSettlement> {
+        self.fetch(&pda::batch(batch_id)).await
+    }
+    
+    /// Every program account of type `T`. Accounts on an older layout are
+    /// skipped until migrated.
+    pub async fn all<T: AccountDeserialize + Discriminator>(&self) -> Result<Vec<(Pubkey, T)>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                0,
+                T::DISCRIMINATOR.to_vec(),
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        let accounts = self
+            .rpc
+            .get_program_accounts_with_config(&shadow_protocol::ID, config)
+            .await?;
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(address, account)| decode(&account.data).ok().map(|decoded| (address, decoded)))
+            .collect())
+    }
+    
+    /// Create a sealed-bid auction funded from the payer's associated token
+    /// account, taking bids in `payment_mint` (the native mint for SOL).
+    /// Returns the new auction id. Programmable NFTs need the
+    /// token-metadata accounts appended, so build those with
+    /// [`instructions::create_sealed_auction`] instead.
+    pub async fn create_sealed_auction(
+        &self,
+        payment_mint: Pubkey,
+        args: instruction::CreateSealedAuction,
+    ) -> Result<(u64, Signature)> {
+        let auction_id = self.protocol_state().await?.next_auction_id;
+        let asset_mint = args.asset_mint;
+        let ix = instructions::create_sealed_auction(
+            accounts::CreateSealedAuction {
+                creator: self.payer(),
+                auction: pda::auction(auction_id),
+                protocol_state: pda::protocol(),
+                asset_mint,
+                payment_mint,
+                asset_vault: pda::asset_vault(auction_id),
+                creator_asset_account: get_associated_token_address(&self.payer(), &asset_mint),
+                asset_metadata: args.nft.map(|_| pda::token_metadata(&asset_mint)),
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            },
+            args,
+        );
+        Ok((auction_id, self.send(&[ix], &[]).await?))
+    }
+    
+    /// Create a Dutch auction funded from the payer's associated token
+    /// account, taking bids in `payment_mint`. Returns the new auction id.
+    pub async fn create_dutch_auction(
+        &self,
+        payment_mint: Pubkey,
+        args: instruction::CreateDutchAuction,
+    ) -> Result<(u64, Signature)> {
+        let auction_id = self.protocol_state().await?.next_auction_id;
+        let asset_mint = args.asset_mint;
+        let ix = instructions::create_dutch_auction(
+            accounts::CreateDutchAuction {
+                creator: self.payer(),
+                auction: pda::auction(auction_id),
+                protocol_state: pda::protocol(),
+                asset_mint,
+                payment_mint,
+                asset_vault: pda::asset_vault(auction_id),
+                creator_asset_account: get_associated_token_address(&self.payer(), &asset_mint),
+                asset_metadata: args.nft.map(|_| pda::token_metadata(&asset_mint)),
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            },
+            args,
+        );
+        Ok((auction_id, self.send(&[ix], &[]).await?))
+    }
+    
+    /// Submit an encrypted bid, escrowing collateral in the auction's payment
+    /// mint from the payer's associated token account. The bid bond and USD
+    /// price feed accounts are filled in from the auction; gated auctions
+    /// need the gate accounts, so build those with
+    /// [`instructions::submit_encrypted_bid`].
+    pub async fn submit_encrypted_bid(
+        &self,
+        args: instruction::SubmitEncryptedBid,
+    ) -> Result<Signature> {
+        let auction_id = args.auction_id;
+        let auction = self.auction(auction_id).await?;
+        let collateral_mint = auction.payment_mint;
+        let bidder = self.payer();
+        let ix = instructions::submit_encrypted_bid(
+            accounts::SubmitBid {
+                bidder,
+                auction: pda::auction(auction_id),
+                bid: pda::bid(auction_id, &bidder),
+                protocol_state: pda::protocol(),
+                bidder_collateral_account: get_associated_token_address(&bidder, &collateral_mint),
+                bid_escrow: pda::bid_escrow(auction_id, &bidder),
+                collateral_mint,
+                bidder_stats: pda::bidder_stats(&bidder),
+                creator_bond_account: (auction.bid_bond > 0)
+                    .then(|| get_associated_token_address(&auction.creator, &collateral_mint)),
+                price_feed: auction.usd_pricing.map(|pricing| pricing.price_feed),
+                gate_token_account: None,
+                gate_attester: None,
+                token_program: anchor_spl::token::ID,
+                system_program: system_program::ID,
+            },
+            args,
+        );
+        self.send(&[ix], &[]).await
+    }
+    
+    /// Crank an auction past its deadline into settlement. Anyone may call
+    /// this; the payer collects the auction's keeper bounty, if any.
+    pub async fn settle_auction(&self, auction_id: u64) -> Result<Signature> {
+        let ix = instructions::settle_auction(
+            accounts::SettleAuction {
+                settler: self.payer(),
+                auction: pda::auction(auction_id),
+                protocol_state: pda::protocol(),
+                system_program: system_program::ID,
+            },
+            instruction::SettleAuction {
+                auction_id,
+                computation_offset: 0,
+            },
+        );
+        self.send(&[ix], &[]).await
+    }    
+    /// Execute an authorized settlement as the winner, paying
+    /// `winning_amount` from the payer's associated token account, or in
+    /// lamports for native SOL auctions. The winning bid, referrer and price
+    /// feed accounts are filled in from the auction. Assets whose Metaplex
+    /// metadata lists creators need the royalty accounts appended (and pNFTs
+    /// the transfer accounts), so build those with
+    /// [`instructions::execute_settlement`] and [`instructions::with_remaining`].
+    pub async fn execute_settlement(&self, auction_id: u64, winning_amount: u64) -> Result<Signature> {
+        let auction = self.auction(auction_id).await?;
+        let winner = self.payer();
+        
+        // Dutch wins have no bid account
+        let winning_bid = (auction.auction_type != AuctionType::Dutch).then(|| pda::bid(auction_id, &winner));
+        let referrer = match winning_bid {
+            Some(address) if auction.referral_fee_bps > 0 => self.fetch::<BidAccount>(&address).await?.referrer,
+            _ => None,
+        };
+        let price_feed = auction.usd_pricing.map(|pricing| pricing.price_feed);
+        let asset_metadata = pda::token_metadata(&auction.asset_mint);
+        let winner_asset_account = get_associated_token_address(&winner, &auction.asset_mint);
+        
+        let ix = if auction.payment_mint == NATIVE_SOL_MINT {
+            instructions::execute_sol_settlement(
+                accounts::ExecuteSolSettlement {
+                    winner,
+                    auction: pda::auction(auction_id),
+                    protocol_state: pda::protocol(),
+                    asset_vault: pda::asset_vault(auction_id),
+                    winner_asset_account,
+                    asset_mint: auction.asset_mint,
+                    creator: auction.creator,
+                    sol_fee_vault: pda::sol_fee_vault(),
+                    winning_bid,
+                    referrer,
+                    price_feed,
+                    asset_metadata,
+                    seller_stats: pda::seller_stats(&auction.creator, &NATIVE_SOL_MINT),
+                    token_program: anchor_spl::token::ID,
+                    system_program: system_program::ID,
+                },
+                instruction::ExecuteSolSettlement {
+                    auction_id,
+                    winner,
+                    winning_amount,
+                },
+            )
+        } else {
+            let payment_mint = auction.payment_mint;
+            instructions::execute_settlement(
+                accounts::ExecuteSettlement {
+                    winner,
+                    auction: pda::auction(auction_id),
+                    protocol_state: pda::protocol(),
+                    asset_vault: pda::asset_vault(auction_id),
+                    winner_asset_account,
+                    winner_payment_account: get_associated_token_address(&winner, &payment_mint),
+                    creator_payment_account: get_associated_token_address(&auction.creator, &payment_mint),
+                    payment_mint,
+                    treasury: pda::treasury(),
+                    protocol_fee_account: pda::fee_vault(&payment_mint),
+                    winning_bid,
+                    referrer_payment_account: referrer
+                        .map(|referrer| get_associated_token_address(&referrer, &payment_mint)),
+                    price_feed,
+                    asset_metadata,
+                    asset_mint: auction.asset_mint,
+                    seller_stats: pda::seller_stats(&auction.creator, &payment_mint),
+                    token_program: anchor_spl::token::ID,
+                    system_program: system_program::ID,
+                },
+                instruction::ExecuteSettlement {
+                    auction_id,
+                    winner,
+                    winning_amount,
+                },
+            )
+        };
+        self.send(&[ix], &[]).await
+    }
+    
+    /// Reclaim the payer's collateral from a losing bid (or a Dutch bid) once
+    /// the auction is settled or cancelled, from the token escrow or, for bids
+    /// placed with native SOL, the lamport escrow. The bid's anti-spam deposit
+    /// comes back with it.
+    pub async fn claim_refund(&self, auction_id: u64) -> Result<Signature> {
+        let bidder = self.payer();
+        let bid_address = pda::bid(auction_id, &bidder);
+        let sol_escrow = pda::sol_escrow(auction_id, &bidder);
+        
+        // Dutch bids escrow collateral without a bid account
+        let bid = match self.fetch::<BidAccount>(&bid_address).await {
+            Ok(bid) => Some(bid),
+            Err(ClientError::AccountNotFound(_)) => None,
+            Err(err) => return Err(err),
+        };
+        
+        let collateral_account = match &bid {
+            Some(bid) if bid.collateral_account != sol_escrow => Some(bid.collateral_account),
+            Some(_) => None,
+            None => {
+                let payment_mint = self.auction(auction_id).await?.payment_mint;
+                (payment_mint != NATIVE_SOL_MINT)
+                    .then(|| get_associated_token_address(&bidder, &payment_mint))
+            }
+        };
+        
+        let ix = match collateral_account {
+            Some(bidder_collateral_account) => instructions::claim_refund(
+                accounts::ClaimRefund {
+                    bidder,
+                    auction: pda::auction(auction_id),
+                    bid: bid.map(|_| bid_address),
+                    bid_escrow: pda::bid_escrow(auction_id, &bidder),
+                    bidder_collateral_account,
+                    token_program: anchor_spl::token::ID,
+                },
+                instruction::ClaimRefund { auction_id },
+            ),
+            None => instructions::claim_sol_refund(
+                accounts::ClaimSolRefund {
+                    bidder,
+                    auction: pda::auction(auction_id),
+                    bid: bid.map(|_| bid_address),
+                    sol_escrow,
+                },
+                instruction::ClaimSolRefund { auction_id },
+            ),
+        };
+        self.send(&[ix], &[]).await
+    }
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+    
+    #[error("Failed to decode account: {0}")]
+    Decode(#[from] anchor_lang::error::Error),
+    
+    #[error("Account {0} not found")]
+    AccountNotFound(Pubkey),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
\ No newline at end of file
@@ -0,0 +1,306 @@
+//! Typed builders for every program instruction.
+//!
+//! Each builder pairs the program's generated `accounts` struct with its
+//! `instruction` argument struct, so a missing account or argument is a
+//! compile error. Optional accounts are `None` when omitted. Instructions
+//! that read remaining accounts (royalty creators, pNFT transfer accounts,
+//! bundle vaults, batched auctions) take them through [`with_remaining`].
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use shadow_protocol::{accounts, instruction};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+
+/// Build a program instruction from any accounts and argument structs
+pub fn build(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: shadow_protocol::ID,
+        accounts: accounts.to_account_metas(None),
+        data: args.data(),
+    }
+}
+
+/// Append remaining accounts to a built instruction
+pub fn with_remaining(mut ix: Instruction, remaining: impl IntoIterator<Item = AccountMeta>) -> Instruction {
+    ix.accounts.extend(remaining);
+    ix
+}
+
+macro_rules! builders {
+    ($($(#[$doc:meta])* $name:ident => $accounts:ident, $args:ident;)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $name(accounts: accounts::$accounts, args: instruction::$args) -> Instruction {
+                build(accounts, args)
+            }
+        )*
+    };
+}
+
+builders! {
+    /// Initialize a new sealed-bid auction
+    create_sealed_auction => CreateSealedAuction, CreateSealedAuction;
+    /// Initialize a new Dutch auction with hidden reserve
+    create_dutch_auction => CreateDutchAuction, CreateDutchAuction;
+    /// Initialize a new multi-unit sealed-bid auction with uniform clearing price
+    create_multi_unit_auction => CreateMultiUnitAuction, CreateMultiUnitAuction;
+    /// Initialize a new reverse (procurement) auction where the lowest ask wins
+    create_reverse_auction => CreateReverseAuction, CreateReverseAuction;
+    /// Initialize a sealed-bid auction selling several SPL assets as one lot
+    create_bundle_auction => CreateBundleAuction, CreateBundleAuction;
+    /// Cancel a bundle auction and return every bundled asset to its creator
+    cancel_bundle_auction => CancelBundleAuction, CancelBundleAuction;
+    /// Publish title, description and media URI for an auction
+    create_auction_metadata => CreateAuctionMetadata, CreateAuctionMetadata;
+    /// Edit an auction's metadata before the first bid
+    update_auction_metadata => UpdateAuctionMetadata, UpdateAuctionMetadata;
+    /// Cancel an auction and return the vault contents to its creator
+    cancel_auction => CancelAuction, CancelAuction;
+    /// Submit an encrypted bid to a sealed auction
+    submit_encrypted_bid => SubmitBid, SubmitEncryptedBid;
+    /// Submit a bid to a Dutch auction
+    submit_dutch_bid => SubmitDutchBid, SubmitDutchBid;
+    /// Restrict bidding on an auction to an allowlist, token holders or attested bidders
+    set_auction_gate => UpdateBidRules, SetAuctionGate;
+    /// Escrow a SOL bounty for the keeper that cranks settlement
+    fund_keeper_bounty => FundKeeperBounty, FundKeeperBounty;
+    /// Denominate an auction's minimum bid, Dutch floor and reserve in USD
+    set_usd_pricing => UpdateUsdPricing, SetUsdPricing;
+    /// Set the non-refundable bond bidders pay the seller
+    set_bid_bond => UpdateBidRules, SetBidBond;
+    /// Submit an encrypted (price, quantity) bid to a multi-unit auction
+    submit_multi_unit_bid => SubmitBid, SubmitMultiUnitBid;
+    /// Submit an encrypted ask to a reverse auction
+    submit_encrypted_ask => SubmitBid, SubmitEncryptedAsk;
+    /// Reclaim escrowed collateral from a losing, cancelled or Dutch bid
+    claim_refund => ClaimRefund, ClaimRefund;
+    /// Submit an encrypted bid to a sealed auction with native SOL collateral
+    submit_encrypted_bid_sol => SubmitSolBid, SubmitEncryptedBidSol;
+    /// Take a Dutch auction with native SOL collateral
+    submit_dutch_bid_sol => SubmitDutchSolBid, SubmitDutchBidSol;
+    /// Reclaim native SOL collateral and close its escrow
+    claim_sol_refund => ClaimSolRefund, ClaimSolRefund;
+    /// Pay out an authorized auction and release the asset to the winner
+    execute_settlement => ExecuteSettlement, ExecuteSettlement;
+    /// Settle an auction paid in native SOL
+    execute_sol_settlement => ExecuteSolSettlement, ExecuteSolSettlement;
+    /// Authorize settlement after MPC computation verification
+    authorize_settlement => AuthorizeSettlement, AuthorizeSettlement;
+    /// Trigger auction settlement (for sealed-bid auctions)
+    settle_auction => SettleAuction, SettleAuction;
+    /// Process batch settlement of multiple auctions
+    batch_settle => BatchSettle, BatchSettle;
+    /// Apply the per-auction results of a batch settlement
+    apply_batch_settlement => ApplyBatchSettlement, ApplyBatchSettlement;
+    /// Close a finished batch settlement to reclaim rent
+    close_batch => CloseBatch, CloseBatch;
+    /// Close a finished auction and its vaults, returning rent to the creator
+    close_auction => CloseAuction, CloseAuction;
+    /// Close a settled or refunded bid and its escrow
+    close_bid => CloseBid, CloseBid;
+    /// Close a settled batch auction order
+    close_batch_order => CloseBatchOrder, CloseBatchOrder;
+    /// Record the clearing price and unit allocations of a multi-unit auction
+    authorize_multi_unit_settlement => AuthorizeMultiUnitSettlement, AuthorizeMultiUnitSettlement;
+    /// Settle a bundle auction, transferring every bundled asset atomically
+    execute_bundle_settlement => ExecuteBundleSettlement, ExecuteBundleSettlement;
+    /// Record the winning supplier and asks of a reverse auction
+    authorize_reverse_settlement => AuthorizeReverseSettlement, AuthorizeReverseSettlement;
+    /// Award a reverse auction to the winning supplier
+    execute_reverse_settlement => ExecuteReverseSettlement, ExecuteReverseSettlement;
+    /// Settle a single bid of a cleared multi-unit auction
+    settle_multi_unit_bid => SettleMultiUnitBid, SettleMultiUnitBid;
+    /// Open a batch auction epoch for a base/quote token pair
+    create_batch_auction => CreateBatchAuction, CreateBatchAuction;
+    /// Submit an encrypted limit order to a batch auction epoch
+    submit_batch_order => SubmitBatchOrder, SubmitBatchOrder;
+    /// Record the uniform clearing price and order fills of a batch epoch
+    record_batch_fills => RecordBatchFills, RecordBatchFills;
+    /// Pay out a single order of a cleared batch epoch
+    settle_batch_order => SettleBatchOrder, SettleBatchOrder;
+    /// Initialize the protocol (one-time setup)
+    initialize_protocol => InitializeProtocol, InitializeProtocol;
+    /// Emergency pause functionality
+    set_pause_state => SetPauseState, SetPauseState;
+    /// Pause individual operations or auction types (PAUSE_* bitmask)
+    set_pause_flags => SetPauseState, SetPauseFlags;
+    /// Freeze or unfreeze a single auction
+    set_auction_frozen => SetAuctionFrozen, SetAuctionFrozen;
+    /// Queue a protocol fee change behind the fee update timelock
+    queue_protocol_fee_update => QueueProtocolFeeUpdate, QueueProtocolFeeUpdate;
+    /// Queue a fee recipient change behind the fee update timelock
+    queue_fee_recipient_update => QueueFeeRecipientUpdate, QueueFeeRecipientUpdate;
+    /// Apply queued fee changes once their timelock has elapsed
+    apply_fee_update => ApplyFeeUpdate, ApplyFeeUpdate;
+    /// Cancel queued fee changes
+    cancel_fee_update => CancelFeeUpdate, CancelFeeUpdate;
+    /// Change the delay applied to future fee updates (admin only)
+    set_fee_update_delay => SetFeeUpdateDelay, SetFeeUpdateDelay;
+    /// Publish a price on a mock oracle feed for local testing
+    #[cfg(feature = "mock-oracle")]
+    set_mock_price => SetMockPrice, SetMockPrice;
+    /// Set the anti-spam bid deposit and per-wallet open bid cap
+    set_bid_limits => SetBidLimits, SetBidLimits;
+    /// Queue a referral fee change behind the fee update timelock
+    queue_referral_fee_update => UpdateFeeConfig, QueueReferralFeeUpdate;
+    /// Queue a fee tier replacement behind the fee update timelock
+    queue_fee_tiers_update => UpdateFeeConfig, QueueFeeTiersUpdate;
+    /// Create the treasury fee vault for a mint
+    initialize_fee_vault => InitializeFeeVault, InitializeFeeVault;
+    /// Create a seller's volume stats for a payment mint
+    initialize_seller_stats => InitializeSellerStats, InitializeSellerStats;
+    /// Withdraw collected fees for one mint to the fee recipient
+    withdraw_fees => WithdrawFees, WithdrawFees;
+    /// Withdraw protocol fees collected in native SOL to the fee recipient
+    withdraw_sol_fees => WithdrawSolFees, WithdrawSolFees;
+    /// Create the roles account for delegated admin roles
+    initialize_roles => InitializeRoles, InitializeRoles;
+    /// Grant an admin role to a key
+    grant_role => ManageRole, GrantRole;
+    /// Revoke an admin role from a key
+    revoke_role => ManageRole, RevokeRole;
+    /// Require M-of-N multisig approval for sensitive admin actions
+    configure_multisig => ConfigureMultisig, ConfigureMultisig;
+    /// Propose a multisig-controlled admin action
+    create_proposal => CreateProposal, CreateProposal;
+    /// Approve a pending admin proposal
+    approve_proposal => ApproveProposal, ApproveProposal;
+    /// Execute an admin proposal that reached its threshold
+    execute_proposal => ExecuteProposal, ExecuteProposal;
+    /// Initiate protocol authority transfer (admin only, first step)
+    initiate_authority_transfer => InitiateAuthorityTransfer, InitiateAuthorityTransfer;
+    /// Accept protocol authority transfer (pending authority, second step after timelock)
+    accept_authority => AcceptAuthority, AcceptAuthority;
+    /// Cancel pending authority transfer (admin only)
+    cancel_authority_transfer => CancelAuthorityTransfer, CancelAuthorityTransfer;
+    /// Upgrade the protocol state to the current layout
+    migrate_protocol_state => MigrateProtocolState, MigrateProtocolState;
+    /// Upgrade an auction account to the current layout
+    migrate_auction => MigrateAuction, MigrateAuction;
+    /// Upgrade a bid account to the current layout
+    migrate_bid => MigrateBid, MigrateBid;
+    /// Upgrade a batch settlement account to the current layout
+    migrate_batch => MigrateBatch, MigrateBatch;
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{system_program, Discriminator};
+    use solana_sdk::pubkey::Pubkey;
+    
+    use crate::pda;
+    
+    fn settle_auction_ix(settler: Pubkey) -> Instruction {
+        settle_auction(
+            accounts::SettleAuction {
+                settler,
+                auction: pda::auction(5),
+                protocol_state: pda::protocol(),
+                system_program: system_program::ID,
+            },
+            instruction::SettleAuction {
+                auction_id: 5,
+                computation_offset: 0,
+            },
+        )
+    }
+    
+    fn execute_settlement_accounts(winner: Pubkey, winning_bid: Option<Pubkey>) -> accounts::ExecuteSettlement {
+        let asset_mint = Pubkey::new_unique();
+        let payment_mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        accounts::ExecuteSettlement {
+            winner,
+            auction: pda::auction(5),
+            protocol_state: pda::protocol(),
+            asset_vault: pda::asset_vault(5),
+            winner_asset_account: Pubkey::new_unique(),
+            winner_payment_account: Pubkey::new_unique(),
+            creator_payment_account: Pubkey::new_unique(),
+            payment_mint,
+            treasury: pda::treasury(),
+            protocol_fee_account: pda::fee_vault(&payment_mint),
+            winning_bid,
+            referrer_payment_account: None,
+            price_feed: None,
+            asset_metadata: pda::token_metadata(&asset_mint),
+            asset_mint,
+            seller_stats: pda::seller_stats(&creator, &payment_mint),
+            token_program: anchor_spl::token::ID,
+            system_program: system_program::ID,
+        }
+    }
+    
+    #[test]
+    fn builds_against_the_program() {
+        let settler = Pubkey::new_unique();
+        let ix = settle_auction_ix(settler);
+        
+        assert_eq!(ix.program_id, shadow_protocol::ID);
+        assert_eq!(&ix.data[..8], instruction::SettleAuction::DISCRIMINATOR);
+        assert_eq!(&ix.data[8..16], &5u64.to_le_bytes());
+    }
+    
+    #[test]
+    fn account_metas_follow_the_accounts_struct() {
+        let settler = Pubkey::new_unique();
+        let ix = settle_auction_ix(settler);
+        
+        assert_eq!(
+            ix.accounts,
+            vec![
+                AccountMeta::new(settler, true),
+                AccountMeta::new(pda::auction(5), false),
+                AccountMeta::new_readonly(pda::protocol(), false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ]
+        );
+    }
+    
+    #[test]
+    fn execute_settlement_encodes_winner_and_amount() {
+        let winner = Pubkey::new_unique();
+        let ix = execute_settlement(
+            execute_settlement_accounts(winner, Some(pda::bid(5, &winner))),
+            instruction::ExecuteSettlement {
+                auction_id: 5,
+                winner,
+                winning_amount: 1_000,
+            },
+        );
+        
+        assert_eq!(&ix.data[..8], instruction::ExecuteSettlement::DISCRIMINATOR);
+        assert_eq!(&ix.data[8..16], &5u64.to_le_bytes());
+        assert_eq!(&ix.data[16..48], winner.as_ref());
+        assert_eq!(&ix.data[48..56], &1_000u64.to_le_bytes());
+        
+        assert_eq!(ix.accounts[0], AccountMeta::new(winner, true));
+        assert_eq!(ix.accounts[10], AccountMeta::new_readonly(pda::bid(5, &winner), false));
+    }
+    
+    #[test]
+    fn omitted_optional_accounts_are_the_program_id() {
+        let winner = Pubkey::new_unique();
+        let ix = execute_settlement(
+            execute_settlement_accounts(winner, None),
+            instruction::ExecuteSettlement {
+                auction_id: 5,
+                winner,
+                winning_amount: 1_000,
+            },
+        );
+        
+        // winning_bid, referrer_payment_account and price_feed
+        for meta in &ix.accounts[10..13] {
+            assert_eq!(*meta, AccountMeta::new_readonly(shadow_protocol::ID, false));
+        }
+    }
+    
+    #[test]
+    fn remaining_accounts_are_appended() {
+        let royalty = AccountMeta::new(Pubkey::new_unique(), false);
+        let ix = with_remaining(settle_auction_ix(Pubkey::new_unique()), [royalty.clone()]);
+        
+        assert_eq!(ix.accounts.len(), 5);
+        assert_eq!(ix.accounts[4], royalty);
+    }
+}
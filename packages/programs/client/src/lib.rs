@@ -0,0 +1,29 @@
+//! Rust client SDK for the Shadow Protocol program.
+//!
+//! - [`pda`] derives every program address from the on-chain seeds.
+//! - [`instructions`] has a typed builder for each program instruction.
+//! - [`decode`] and [`ShadowClient::fetch`] deserialize program accounts.
+//! - [`ShadowClient`] wraps the common flows (create, bid, settle, refund)
+//!   in async helpers that derive the accounts and send the transaction.
+//!
+//! ```no_run
+//! # async fn example(payer: solana_sdk::signature::Keypair) -> shadow_protocol_client::Result<()> {
+//! use shadow_protocol_client::ShadowClient;
+//!
+//! let client = ShadowClient::new("http://127.0.0.1:8899", payer);
+//! let auction = client.auction(0).await?;
+//! if auction.status == shadow_protocol_client::state::AuctionStatus::Active {
+//!     client.settle_auction(0).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod client;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+
+pub use client::{decode, ShadowClient};
+pub use error::{ClientError, Result};
+pub use shadow_protocol::{accounts, instruction, state, ID};
\ No newline at end of file
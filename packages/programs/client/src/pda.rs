@@ -0,0 +1,198 @@
+//! Program-derived addresses, mirroring the seeds in `shadow_protocol::state`.
+
+use shadow_protocol::state::*;
+use solana_sdk::pubkey::Pubkey;
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &shadow_protocol::ID).0
+}
+
+/// Global protocol state
+pub fn protocol() -> Pubkey {
+    find(&[PROTOCOL_SEED])
+}
+
+/// Role registry
+pub fn roles() -> Pubkey {
+    find(&[ROLES_SEED])
+}
+
+/// Admin multisig configuration
+pub fn multisig() -> Pubkey {
+    find(&[MULTISIG_SEED])
+}
+
+/// Multisig proposal
+pub fn proposal(proposal_id: u64) -> Pubkey {
+    find(&[PROPOSAL_SEED, &proposal_id.to_le_bytes()])
+}
+
+/// Treasury authority over the per-mint fee vaults
+pub fn treasury() -> Pubkey {
+    find(&[TREASURY_SEED])
+}
+
+/// Fee vault for a payment mint
+pub fn fee_vault(mint: &Pubkey) -> Pubkey {
+    find(&[FEE_VAULT_SEED, mint.as_ref()])
+}
+
+/// Fee ledger for a payment mint
+pub fn fee_ledger(mint: &Pubkey) -> Pubkey {
+    find(&[FEE_LEDGER_SEED, mint.as_ref()])
+}
+
+/// Lamport fee vault for native SOL auctions
+pub fn sol_fee_vault() -> Pubkey {
+    find(&[SOL_FEE_VAULT_SEED])
+}
+
+/// Auction account
+pub fn auction(auction_id: u64) -> Pubkey {
+    find(&[AUCTION_SEED, &auction_id.to_le_bytes()])
+}
+
+/// Vault holding the auctioned asset
+pub fn asset_vault(auction_id: u64) -> Pubkey {
+    find(&[ASSET_VAULT_SEED, &auction_id.to_le_bytes()])
+}
+
+/// Vault holding a reverse auction's budget
+pub fn budget_vault(auction_id: u64) -> Pubkey {
+    find(&[BUDGET_VAULT_SEED, &auction_id.to_le_bytes()])
+}
+
+/// Listing metadata
+pub fn auction_metadata(auction_id: u64) -> Pubkey {
+    find(&[AUCTION_METADATA_SEED, &auction_id.to_le_bytes()])
+}
+
+/// Bundle manifest (bundle auctions)
+pub fn bundle(auction_id: u64) -> Pubkey {
+    find(&[BUNDLE_SEED, &auction_id.to_le_bytes()])
+}
+
+/// Quote vault (batch auctions)
+pub fn quote_vault(auction_id: u64) -> Pubkey {
+    find(&[QUOTE_VAULT_SEED, &auction_id.to_le_bytes()])
+}
+
+/// Sealed bid
+pub fn bid(auction_id: u64, bidder: &Pubkey) -> Pubkey {
+    find(&[BID_SEED, &auction_id.to_le_bytes(), bidder.as_ref()])
+}
+
+/// Token escrow holding a bid's collateral
+pub fn bid_escrow(auction_id: u64, bidder: &Pubkey) -> Pubkey {
+    find(&[BID_ESCROW_SEED, &auction_id.to_le_bytes(), bidder.as_ref()])
+}
+
+/// Lamport escrow holding a native SOL bid's collateral
+pub fn sol_escrow(auction_id: u64, bidder: &Pubkey) -> Pubkey {
+    find(&[SOL_ESCROW_SEED, &auction_id.to_le_bytes(), bidder.as_ref()])
+}
+
+/// Batch auction order
+pub fn order(auction_id: u64, trader: &Pubkey) -> Pubkey {
+    find(&[ORDER_SEED, &auction_id.to_le_bytes(), trader.as_ref()])
+}
+
+/// Batch settlement
+pub fn batch(batch_id: u64) -> Pubkey {
+    find(&[BATCH_SEED, &batch_id.to_le_bytes()])
+}
+
+/// Bidder's open bid count
+pub fn bidder_stats(bidder: &Pubkey) -> Pubkey {
+    find(&[BIDDER_STATS_SEED, bidder.as_ref()])
+}
+
+/// Seller's settled volume in a payment mint
+pub fn seller_stats(creator: &Pubkey, mint: &Pubkey) -> Pubkey {
+    find(&[SELLER_STATS_SEED, creator.as_ref(), mint.as_ref()])
+}
+
+/// Protocol-owned mock price feed
+#[cfg(feature = "mock-oracle")]
+pub fn mock_price_feed(feed_id: &[u8; 32]) -> Pubkey {
+    find(&[MOCK_PRICE_FEED_SEED, feed_id.as_ref()])
+}
+
+/// Metaplex metadata for a mint
+pub fn token_metadata(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Seeds spelled out as the TypeScript tests and other clients derive them
+    fn expected(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &shadow_protocol::ID).0
+    }
+    
+    #[test]
+    fn global_addresses_match_program_seeds() {
+        assert_eq!(protocol(), expected(&[b"protocol"]));
+        assert_eq!(roles(), expected(&[b"roles"]));
+        assert_eq!(multisig(), expected(&[b"multisig"]));
+        assert_eq!(treasury(), expected(&[b"treasury"]));
+        assert_eq!(sol_fee_vault(), expected(&[b"sol_fee_vault"]));
+    }
+    
+    #[test]
+    fn id_addresses_match_program_seeds() {
+        let id = 42u64.to_le_bytes();
+        assert_eq!(proposal(42), expected(&[b"proposal", &id]));
+        assert_eq!(auction(42), expected(&[b"auction", &id]));
+        assert_eq!(asset_vault(42), expected(&[b"asset_vault", &id]));
+        assert_eq!(budget_vault(42), expected(&[b"budget_vault", &id]));
+        assert_eq!(auction_metadata(42), expected(&[b"auction_metadata", &id]));
+        assert_eq!(bundle(42), expected(&[b"bundle", &id]));
+        assert_eq!(quote_vault(42), expected(&[b"quote_vault", &id]));
+        assert_eq!(batch(42), expected(&[b"batch", &id]));
+    }
+    
+    #[test]
+    fn bidder_addresses_match_program_seeds() {
+        let id = 7u64.to_le_bytes();
+        let bidder = Pubkey::new_unique();
+        assert_eq!(bid(7, &bidder), expected(&[b"bid", &id, bidder.as_ref()]));
+        assert_eq!(bid_escrow(7, &bidder), expected(&[b"bid_escrow", &id, bidder.as_ref()]));
+        assert_eq!(sol_escrow(7, &bidder), expected(&[b"sol_escrow", &id, bidder.as_ref()]));
+        assert_eq!(order(7, &bidder), expected(&[b"order", &id, bidder.as_ref()]));
+        assert_eq!(bidder_stats(&bidder), expected(&[b"bidder_stats", bidder.as_ref()]));
+    }
+    
+    #[test]
+    fn mint_addresses_match_program_seeds() {
+        let mint = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        assert_eq!(fee_vault(&mint), expected(&[b"fee_vault", mint.as_ref()]));
+        assert_eq!(fee_ledger(&mint), expected(&[b"fee_ledger", mint.as_ref()]));
+        assert_eq!(
+            seller_stats(&creator, &mint),
+            expected(&[b"seller_stats", creator.as_ref(), mint.as_ref()])
+        );
+    }
+    
+    #[cfg(feature = "mock-oracle")]
+    #[test]
+    fn mock_price_feed_matches_program_seeds() {
+        let feed_id = [3u8; 32];
+        assert_eq!(mock_price_feed(&feed_id), expected(&[b"mock_price_feed", &feed_id]));
+    }
+    
+    #[test]
+    fn token_metadata_uses_metaplex_seeds() {
+        let mint = Pubkey::new_unique();
+        let metaplex: Pubkey = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s".parse().unwrap();
+        let (address, _) =
+            Pubkey::find_program_address(&[b"metadata", metaplex.as_ref(), mint.as_ref()], &metaplex);
+        assert_eq!(token_metadata(&mint), address);
+    }
+}
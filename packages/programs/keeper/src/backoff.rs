@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use solana_sdk::pubkey::Pubkey;
+
+// Delay before the first retry
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+// Upper bound for retry delays and target cooldowns
+const MAX_DELAY: Duration = Duration::from_secs(300);
+
+/// Exponential delay for the given attempt (0-based), capped at `MAX_DELAY`
+pub fn retry_delay(attempt: u32) -> Duration {
+    BASE_DELAY
+        .checked_mul(1u32 << attempt.min(16))
+        .map_or(MAX_DELAY, |delay| delay.min(MAX_DELAY))
+}
+
+/// Tracks targets whose transactions keep failing so that a stuck auction
+/// (frozen, paused, misconfigured) is not retried on every scan.
+#[derive(Default)]
+pub struct Cooldowns {
+    failures: HashMap<Pubkey, (u32, Instant)>,
+}
+
+impl Cooldowns {
+    /// Whether `target` may be attempted now
+    pub fn ready(&self, target: &Pubkey) -> bool {
+        self.failures
+            .get(target)
+            .map_or(true, |(_, retry_at)| Instant::now() >= *retry_at)
+    }
+    
+    /// Record a failed attempt, doubling the target's cooldown
+    pub fn failed(&mut self, target: Pubkey) {
+        let failures = self.failures.get(&target).map_or(0, |(count, _)| *count);
+        let retry_at = Instant::now() + retry_delay(failures + 4);
+        self.failures.insert(target, (failures + 1, retry_at));
+    }
+    
+    /// Clear the target's failure history
+    pub fn succeeded(&mut self, target: &Pubkey) {
+        self.failures.remove(target);
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn retry_delay_doubles_from_base() {
+        assert_eq!(retry_delay(0), BASE_DELAY);
+        assert_eq!(retry_delay(1), BASE_DELAY * 2);
+        assert_eq!(retry_delay(3), BASE_DELAY * 8);
+    }
+    
+    #[test]
+    fn retry_delay_is_capped() {
+        assert_eq!(retry_delay(10), MAX_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_DELAY);
+    }
+    
+    #[test]
+    fn unknown_targets_are_ready() {
+        let cooldowns = Cooldowns::default();
+        assert!(cooldowns.ready(&Pubkey::new_unique()));
+    }
+    
+    #[test]
+    fn failure_puts_only_that_target_on_cooldown() {
+        let mut cooldowns = Cooldowns::default();
+        let target = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        
+        cooldowns.failed(target);
+        assert!(!cooldowns.ready(&target));
+        assert!(cooldowns.ready(&other));
+    }
+    
+    #[test]
+    fn cooldown_grows_with_consecutive_failures() {
+        let mut cooldowns = Cooldowns::default();
+        let target = Pubkey::new_unique();
+        
+        cooldowns.failed(target);
+        let (count, first) = cooldowns.failures[&target];
+        assert_eq!(count, 1);
+        
+        cooldowns.failed(target);
+        let (count, second) = cooldowns.failures[&target];
+        assert_eq!(count, 2);
+        assert!(second - first >= retry_delay(5) - retry_delay(4));
+    }
+    
+    #[test]
+    fn success_clears_the_cooldown() {
+        let mut cooldowns = Cooldowns::default();
+        let target = Pubkey::new_unique();
+        
+        cooldowns.failed(target);
+        cooldowns.succeeded(&target);
+        assert!(cooldowns.ready(&target));
+        assert!(!cooldowns.failures.contains_key(&target));
+    }
+}
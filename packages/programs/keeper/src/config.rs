@@ -0,0 +1,61 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use solana_sdk::pubkey::Pubkey;
+
+/// Command line options, each overridable through the environment
+#[derive(Parser, Debug)]
+#[command(
+    name = "shadow-keeper",
+    version,
+    about = "Settles ended Shadow Protocol auctions",
+    long_about = "Settles ended Shadow Protocol auctions.\n\n\
+        The keeper cranks the permissionless settle_auction. It does not run \
+        execute_settlement, which the winner must sign because the winner \
+        pays the winning amount and only the winner knows their sealed bid."
+)]
+pub struct Config {
+    /// RPC endpoint of the cluster to crank
+    #[arg(long, env = "SHADOW_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    pub rpc_url: String,
+    
+    /// Keypair that signs and pays for keeper transactions
+    /// (defaults to the Solana CLI keypair)
+    #[arg(long, env = "SHADOW_KEEPER_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+    
+    /// Shadow Protocol program id
+    #[arg(long, env = "SHADOW_PROGRAM_ID", default_value_t = shadow_protocol::ID)]
+    pub program_id: Pubkey,
+    
+    /// Seconds between scans
+    #[arg(long, env = "SHADOW_KEEPER_INTERVAL", default_value_t = 10)]
+    pub interval_secs: u64,
+    
+    /// Attempts per transaction before the target is put on cooldown
+    #[arg(long, env = "SHADOW_KEEPER_MAX_RETRIES", default_value_t = 5)]
+    pub max_retries: u32,
+    
+    /// Address the Prometheus metrics endpoint listens on
+    #[arg(long, env = "SHADOW_KEEPER_METRICS_ADDR", default_value = "127.0.0.1:9464")]
+    pub metrics_addr: SocketAddr,
+    
+    /// Run a single scan and exit
+    #[arg(long)]
+    pub once: bool,
+}
+
+impl Config {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(1))
+    }
+    
+    pub fn keypair_path(&self) -> PathBuf {
+        self.keypair.clone().unwrap_or_else(|| {
+            let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+            PathBuf::from(home).join(".config/solana/id.json")
+        })
+    }
+}
\ No newline at end of file
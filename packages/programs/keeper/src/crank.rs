@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anchor_lang::{system_program, AccountDeserialize, InstructionData, ToAccountMetas};
+use shadow_protocol::state::*;
+use shadow_protocol::{accounts, instruction};
+use solana_client::client_error::ClientError;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::backoff::{retry_delay, Cooldowns};
+use crate::metrics::{Action, Metrics};
+use crate::scanner;
+
+/// Scans the program and submits whatever each auction needs next.
+///
+/// `settle_auction` is permissionless and is cranked for every auction past
+/// its deadline. Refunds and account closures must be signed by the bidder
+/// or seller, so those only run for bids and auctions owned by the keeper
+/// wallet. `execute_settlement` is never cranked: the winner signs it and
+/// pays the amount of their sealed bid, which the keeper cannot know.
+pub struct Keeper {
+    rpc: RpcClient,
+    payer: Keypair,
+    program_id: Pubkey,
+    max_retries: u32,
+    metrics: Arc<Metrics>,
+    cooldowns: Cooldowns,
+}
+
+impl Keeper {
+    pub fn new(
+        rpc: RpcClient,
+        payer: Keypair,
+        program_id: Pubkey,
+        max_retries: u32,
+        metrics: Arc<Metrics>,
+    ) -> Self {
+        Self {
+            rpc,
+            payer,
+            program_id,
+            max_retries,
+            metrics,
+            cooldowns: Cooldowns::default(),
+        }
+    }
+    
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+    
+    /// One full pass over the program's accounts
+    pub async fn run_once(&mut self) -> anyhow::Result<()> {
+        self.settle_ended_auctions().await?;
+        self.refund_own_bids().await?;
+        self.close_own_auctions().await?;
+        
+        self.metrics.scans.fetch_add(1, Ordering::Relaxed);
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        self.metrics.last_scan_timestamp.store(now, Ordering::Relaxed);
+        Ok(())
+    }
+    
+    async fn settle_ended_auctions(&mut self) -> anyhow::Result<()> {
+        let clock = scanner::fetch_clock(&self.rpc).await?;
+        let auctions = scanner::active_auctions(&self.rpc, &self.program_id).await?;
+        
+        // Batched auctions settle through the batch; frozen ones would revert
+        let ended: Vec<_> = auctions
+            .iter()
+            .filter(|(_, auction)| {
+                scanner::auction_time(auction, &clock) >= auction.end_time
+                    && auction.batch.is_none()
+                    && !auction.frozen
+            })
+            .collect();
+        
+        self.metrics.auctions_active.store(auctions.len() as u64, Ordering::Relaxed);
+        self.metrics.auctions_pending.store(ended.len() as u64, Ordering::Relaxed);
+        
+        for (address, auction) in ended {
+            let ix = Instruction {
+                program_id: self.program_id,
+                accounts: accounts::SettleAuction {
+                    settler: self.payer(),
+                    auction: *address,
+                    protocol_state: self.pda(&[PROTOCOL_SEED]),
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+                data: instruction::SettleAuction {
+                    auction_id: auction.auction_id,
+                    computation_offset: 0,
+                }
+                .data(),
+            };
+            
+            if self.submit(Action::Settle, *address, vec![ix]).await && auction.keeper.is_none() {
+                self.metrics.bounty_lamports.fetch_add(auction.keeper_bounty, Ordering::Relaxed);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Reclaim collateral from the keeper wallet's losing bids on finished
+    /// auctions and close the bid accounts, which unblocks `close_auction`
+    async fn refund_own_bids(&mut self) -> anyhow::Result<()> {
+        let bids = scanner::bids_by_bidder(&self.rpc, &self.program_id, &self.payer()).await?;
+        let auction_ids: Vec<u64> = bids.iter().map(|(_, bid)| bid.auction_id).collect();
+        let auctions = self.fetch_auctions(&auction_ids).await?;
+        let bidder_stats = self.pda(&[BIDDER_STATS_SEED, self.payer().as_ref()]);
+        let has_bidder_stats = self.rpc.get_account(&bidder_stats).await.is_ok();
+        
+        for (address, bid) in bids {
+            let Some(auction) = auctions.get(&bid.auction_id) else {
+                continue;
+            };
+            if !is_finished(auction) {
+                continue;
+            }
+            
+            let auction_address = self.auction_pda(bid.auction_id);
+            let id_bytes = bid.auction_id.to_le_bytes();
+            let sol_escrow = self.pda(&[SOL_ESCROW_SEED, &id_bytes, self.payer().as_ref()]);
+            let bid_escrow = (bid.collateral_account != sol_escrow)
+                .then(|| self.pda(&[BID_ESCROW_SEED, &id_bytes, self.payer().as_ref()]));
+            
+            let mut ixs = Vec::new();
+            let mut action = Action::CloseBid;
+            
+            // Winning allocations are settled by the winner, never refunded
+            if !bid.settled {
+                if bid.is_winner || bid.units_allocated > 0 {
+                    continue;
+                }
+                action = Action::Refund;
+                ixs.push(match bid_escrow {
+                    Some(bid_escrow) => Instruction {
+                        program_id: self.program_id,
+                        accounts: accounts::ClaimRefund {
+                            bidder: self.payer(),
+                            auction: auction_address,
+                            bid: Some(address),
+                            bid_escrow,
+                            bidder_collateral_account: bid.collateral_account,
+                            token_program: anchor_spl::token::ID,
+                        }
+                        .to_account_metas(None),
+                        data: instruction::ClaimRefund { auction_id: bid.auction_id }.data(),
+                    },
+                    None => Instruction {
+                        program_id: self.program_id,
+                        accounts: accounts::ClaimSolRefund {
+                            bidder: self.payer(),
+                            auction: auction_address,
+                            bid: Some(address),
+                            sol_escrow,
+                        }
+                        .to_account_metas(None),
+                        data: instruction::ClaimSolRefund { auction_id: bid.auction_id }.data(),
+                    },
+                });
+            }
+            
+            ixs.push(Instruction {
+                program_id: self.program_id,
+                accounts: accounts::CloseBid {
+                    bidder: self.payer(),
+                    auction: auction_address,
+                    bid: address,
+                    bid_escrow,
+                    bidder_stats: has_bidder_stats.then_some(bidder_stats),
+                    token_program: anchor_spl::token::ID,
+                }
+                .to_account_metas(None),
+                data: instruction::CloseBid { auction_id: bid.auction_id }.data(),
+            });
+            
+            self.submit(action, address, ixs).await;
+        }
+        
+        Ok(())
+    }
+    
+    /// Close the keeper wallet's finished auctions once every bid is closed
+    async fn close_own_auctions(&mut self) -> anyhow::Result<()> {
+        let auctions = scanner::auctions_by_creator(&self.rpc, &self.program_id, &self.payer()).await?;
+        
+        for (address, auction) in auctions {
+            // Dutch auctions settle without bid accounts
+            let expected_closed = if auction.auction_type == AuctionType::Dutch {
+                0
+            } else {
+                auction.bid_count
+            };
+            if !is_finished(&auction) || auction.bids_closed < expected_closed {
+                continue;
+            }
+            
+            let id_bytes = auction.auction_id.to_le_bytes();
+            let metadata = self.pda(&[AUCTION_METADATA_SEED, &id_bytes]);
+            let has_metadata = self.rpc.get_account(&metadata).await.is_ok();
+            
+            let mut metas = accounts::CloseAuction {
+                creator: self.payer(),
+                auction: address,
+                asset_vault: (auction.asset_vault != Pubkey::default()).then_some(auction.asset_vault),
+                quote_vault: (auction.auction_type == AuctionType::Batch)
+                    .then(|| self.pda(&[QUOTE_VAULT_SEED, &id_bytes])),
+                bundle_manifest: auction.bundle,
+                auction_metadata: has_metadata.then_some(metadata),
+                token_program: anchor_spl::token::ID,
+            }
+            .to_account_metas(None);
+            
+            // Bundle vaults follow as remaining accounts
+            if let Some(bundle) = auction.bundle {
+                let data = self.rpc.get_account_data(&bundle).await?;
+                let manifest = BundleManifest::try_deserialize(&mut data.as_slice())?;
+                metas.extend(manifest.items.iter().map(|item| AccountMeta::new(item.vault, false)));
+            }
+            
+            let ix = Instruction {
+                program_id: self.program_id,
+                accounts: metas,
+                data: instruction::CloseAuction { auction_id: auction.auction_id }.data(),
+            };
+            self.submit(Action::CloseAuction, address, vec![ix]).await;
+        }
+        
+        Ok(())
+    }
+    
+    /// Sends `ixs` in one transaction, retrying transport errors with
+    /// exponential backoff. Program errors are not retried within a scan;
+    /// the target is put on cooldown instead. Returns whether it landed.
+    async fn submit(&mut self, action: Action, target: Pubkey, ixs: Vec<Instruction>) -> bool {
+        if !self.cooldowns.ready(&target) {
+            return false;
+        }
+        
+        for attempt in 0..self.max_retries.max(1) {
+            if attempt > 0 {
+                self.metrics.retries.fetch_add(1, Ordering::Relaxed);
+                tokio::time::sleep(retry_delay(attempt - 1)).await;
+            }
+            
+            match self.send(&ixs).await {
+                Ok(signature) => {
+                    log::info!("{} {target}: {signature}", action.label());
+                    self.metrics.record(action, true);
+                    self.cooldowns.succeeded(&target);
+                    return true;
+                }
+                Err(err) if err.get_transaction_error().is_some() => {
+                    log::warn!("{} {target} rejected: {err}", action.label());
+                    break;
+                }
+                Err(err) => {
+                    log::warn!("{} {target} attempt {} failed: {err}", action.label(), attempt + 1);
+                }
+            }
+        }
+        
+        self.metrics.record(action, false);
+        self.cooldowns.failed(target);
+        false
+    }
+    
+    async fn send(&self, ixs: &[Instruction]) -> Result<solana_sdk::signature::Signature, ClientError> {
+        let blockhash = self.rpc.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(ixs, Some(&self.payer()), &[&self.payer], blockhash);
+        self.rpc.send_and_confirm_transaction(&tx).await
+    }
+    
+    /// Fetch the given auctions in batches, keyed by auction id
+    async fn fetch_auctions(&self, auction_ids: &[u64]) -> anyhow::Result<HashMap<u64, AuctionAccount>> {
+        let mut auctions = HashMap::new();
+        for ids in auction_ids.chunks(100) {
+            let addresses: Vec<Pubkey> = ids.iter().map(|id| self.auction_pda(*id)).collect();
+            let accounts = self.rpc.get_multiple_accounts(&addresses).await?;
+            for (id, account) in ids.iter().zip(accounts) {
+                let Some(account) = account else {
+                    continue;
+                };
+                if let Ok(auction) = AuctionAccount::try_deserialize(&mut account.data.as_slice()) {
+                    auctions.insert(*id, auction);
+                }
+            }
+        }
+        Ok(auctions)
+    }
+    
+    fn auction_pda(&self, auction_id: u64) -> Pubkey {
+        self.pda(&[AUCTION_SEED, &auction_id.to_le_bytes()])
+    }
+    
+    fn pda(&self, seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &self.program_id).0
+    }
+}
+
+fn is_finished(auction: &AuctionAccount) -> bool {
+    auction.status == AuctionStatus::Settled || auction.status == AuctionStatus::Cancelled
+}
\ No newline at end of file
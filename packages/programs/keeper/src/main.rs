@@ -0,0 +1,80 @@
+//! Shadow Protocol keeper.
+//!
+//! Polls the program for auctions past their deadline and submits the
+//! settlement crank, collecting any keeper bounty. When the keeper wallet is
+//! also a bidder or seller, it claims refunds and closes finished bids and
+//! auctions it owns. Point it at a local `solana-test-validator` with the
+//! defaults, or see `--help` for the options.
+
+mod backoff;
+mod config;
+mod crank;
+mod metrics;
+mod scanner;
+
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use clap::Parser;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::read_keypair_file;
+
+use crate::config::Config;
+use crate::crank::Keeper;
+use crate::metrics::Metrics;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    
+    let config = Config::parse();
+    let keypair_path = config.keypair_path();
+    let payer = read_keypair_file(&keypair_path)
+        .map_err(|err| anyhow::anyhow!("{err}"))
+        .with_context(|| format!("reading keypair {}", keypair_path.display()))?;
+    
+    let metrics = Arc::new(Metrics::default());
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), CommitmentConfig::confirmed());
+    let mut keeper = Keeper::new(rpc, payer, config.program_id, config.max_retries, metrics.clone());
+    
+    log::info!(
+        "keeper {} cranking program {} on {}",
+        keeper.payer(),
+        config.program_id,
+        config.rpc_url
+    );
+    
+    if !config.once {
+        let addr = config.metrics_addr;
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            if let Err(err) = metrics::serve(addr, metrics).await {
+                log::error!("metrics server stopped: {err}");
+            }
+        });
+    }
+    
+    let mut interval = tokio::time::interval(config.interval());
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+        
+        if let Err(err) = keeper.run_once().await {
+            metrics.scan_errors.fetch_add(1, Ordering::Relaxed);
+            log::error!("scan failed: {err:#}");
+        }
+        
+        if config.once {
+            break;
+        }
+    }
+    
+    log::info!("keeper stopped");
+    Ok(())
+}
\ No newline at end of file
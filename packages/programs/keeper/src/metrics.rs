@@ -0,0 +1,104 @@
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Transactions the keeper submits
+#[derive(Clone, Copy, Debug)]
+pub enum Action {
+    Settle,
+    Refund,
+    CloseBid,
+    CloseAuction,
+}
+
+impl Action {
+    const ALL: [Action; 4] = [Action::Settle, Action::Refund, Action::CloseBid, Action::CloseAuction];
+    
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Settle => "settle",
+            Action::Refund => "refund",
+            Action::CloseBid => "close_bid",
+            Action::CloseAuction => "close_auction",
+        }
+    }
+}
+
+/// Counters exported in the Prometheus text format
+#[derive(Default)]
+pub struct Metrics {
+    pub scans: AtomicU64,
+    pub scan_errors: AtomicU64,
+    pub last_scan_timestamp: AtomicU64,
+    pub auctions_active: AtomicU64,
+    pub auctions_pending: AtomicU64,
+    pub retries: AtomicU64,
+    pub bounty_lamports: AtomicU64,
+    succeeded: [AtomicU64; 4],
+    failed: [AtomicU64; 4],
+}
+
+impl Metrics {
+    pub fn record(&self, action: Action, success: bool) {
+        let counters = if success { &self.succeeded } else { &self.failed };
+        counters[action as usize].fetch_add(1, Ordering::Relaxed);
+    }
+    
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: &AtomicU64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {}", value.load(Ordering::Relaxed));
+        };
+        metric("shadow_keeper_scans_total", "counter", "Completed scans", &self.scans);
+        metric("shadow_keeper_scan_errors_total", "counter", "Scans aborted by RPC errors", &self.scan_errors);
+        metric("shadow_keeper_last_scan_timestamp_seconds", "gauge", "Unix time of the last completed scan", &self.last_scan_timestamp);
+        metric("shadow_keeper_auctions_active", "gauge", "Active auctions seen in the last scan", &self.auctions_active);
+        metric("shadow_keeper_auctions_pending", "gauge", "Active auctions past their deadline in the last scan", &self.auctions_pending);
+        metric("shadow_keeper_retries_total", "counter", "Transaction attempts retried after a transport error", &self.retries);
+        metric("shadow_keeper_bounty_lamports_total", "counter", "Keeper bounties earned", &self.bounty_lamports);
+        
+        let _ = writeln!(out, "# HELP shadow_keeper_transactions_total Keeper transactions by action and result");
+        let _ = writeln!(out, "# TYPE shadow_keeper_transactions_total counter");
+        for action in Action::ALL {
+            for (result, counters) in [("success", &self.succeeded), ("failure", &self.failed)] {
+                let _ = writeln!(
+                    out,
+                    "shadow_keeper_transactions_total{{action=\"{}\",result=\"{result}\"}} {}",
+                    action.label(),
+                    counters[action as usize].load(Ordering::Relaxed),
+                );
+            }
+        }
+        out
+    }
+}
+
+/// Serve `GET /metrics` (any path answers with the metrics) until the
+/// process exits
+pub async fn serve(addr: SocketAddr, metrics: Arc<Metrics>) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("metrics listening on http://{addr}/metrics");
+    
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            if stream.read(&mut request).await.is_err() {
+                return;
+            }
+            let body = metrics.render();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len(),
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
\ No newline at end of file
@@ -0,0 +1,173 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+use shadow_protocol::state::{AuctionAccount, AuctionStatus, BidAccount, TimingMode};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::clock::Clock;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::sysvar;
+
+// Discriminator + auction_id
+const CREATOR_OFFSET: usize = 8 + 8;
+
+// Creator, asset_mint, asset_vault, asset_amount, auction_type
+const STATUS_OFFSET: usize = CREATOR_OFFSET + 32 * 3 + 8 + 1;
+
+// Discriminator + auction_id
+const BIDDER_OFFSET: usize = 8 + 8;
+
+/// Current cluster clock, which auction deadlines are compared against
+pub async fn fetch_clock(rpc: &RpcClient) -> anyhow::Result<Clock> {
+    let account = rpc.get_account(&sysvar::clock::ID).await?;
+    Ok(bincode::deserialize(&account.data)?)
+}
+
+/// Current cluster time in the unit of the auction's deadline
+pub fn auction_time(auction: &AuctionAccount, clock: &Clock) -> i64 {
+    match auction.timing_mode {
+        TimingMode::Timestamp => clock.unix_timestamp,
+        TimingMode::Slot => clock.slot as i64,
+    }
+}
+
+/// Active auctions. Status is a fixed-offset byte, so the filter runs
+/// server-side and finished auctions are never downloaded.
+pub async fn active_auctions(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, AuctionAccount)>> {
+    fetch(
+        rpc,
+        program_id,
+        AuctionAccount::DISCRIMINATOR,
+        Memcmp::new_raw_bytes(STATUS_OFFSET, vec![AuctionStatus::Active as u8]),
+    )
+    .await
+}
+
+/// Auctions created by `creator`
+pub async fn auctions_by_creator(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    creator: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, AuctionAccount)>> {
+    fetch(
+        rpc,
+        program_id,
+        AuctionAccount::DISCRIMINATOR,
+        Memcmp::new_raw_bytes(CREATOR_OFFSET, creator.to_bytes().to_vec()),
+    )
+    .await
+}
+
+/// Sealed bids placed by `bidder`
+pub async fn bids_by_bidder(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    bidder: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, BidAccount)>> {
+    fetch(
+        rpc,
+        program_id,
+        BidAccount::DISCRIMINATOR,
+        Memcmp::new_raw_bytes(BIDDER_OFFSET, bidder.to_bytes().to_vec()),
+    )
+    .await
+}
+
+async fn fetch<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    discriminator: &[u8],
+    filter: Memcmp,
+) -> anyhow::Result<Vec<(Pubkey, T)>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, discriminator.to_vec())),
+            RpcFilterType::Memcmp(filter),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let accounts = rpc.get_program_accounts_with_config(program_id, config).await?;
+    
+    // Accounts still on an older layout fail to decode until migrated
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(address, account)| match T::try_deserialize(&mut account.data.as_slice()) {
+            Ok(decoded) => Some((address, decoded)),
+            Err(err) => {
+                log::debug!("skipping {address}: {err}");
+                None
+            }
+        })
+        .collect())
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Space};
+    use shadow_protocol::state::AuctionType;
+    
+    // An all-zero body decodes to the default of every field
+    fn zeroed<T: AccountDeserialize + Discriminator + Space>() -> T {
+        let mut data = vec![0u8; 8 + T::INIT_SPACE];
+        data[..8].copy_from_slice(T::DISCRIMINATOR);
+        T::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+    
+    fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+    
+    #[test]
+    fn auction_offsets_match_layout() {
+        let creator = Pubkey::new_unique();
+        let mut auction: AuctionAccount = zeroed();
+        auction.auction_id = u64::MAX;
+        auction.creator = creator;
+        auction.asset_mint = Pubkey::new_unique();
+        auction.asset_vault = Pubkey::new_unique();
+        auction.asset_amount = u64::MAX;
+        auction.auction_type = AuctionType::Dutch;
+        auction.status = AuctionStatus::Settled;
+        
+        let data = serialize(&auction);
+        assert_eq!(&data[CREATOR_OFFSET..CREATOR_OFFSET + 32], creator.as_ref());
+        assert_eq!(data[STATUS_OFFSET], AuctionStatus::Settled as u8);
+        assert_eq!(data[STATUS_OFFSET - 1], AuctionType::Dutch as u8);
+    }
+    
+    #[test]
+    fn active_filter_matches_only_active_auctions() {
+        let mut auction: AuctionAccount = zeroed();
+        for (status, expected) in [
+            (AuctionStatus::Created, false),
+            (AuctionStatus::Active, true),
+            (AuctionStatus::Ended, false),
+            (AuctionStatus::Settled, false),
+            (AuctionStatus::Cancelled, false),
+        ] {
+            auction.status = status;
+            let data = serialize(&auction);
+            assert_eq!(data[STATUS_OFFSET] == AuctionStatus::Active as u8, expected);
+        }
+    }
+    
+    #[test]
+    fn bidder_offset_matches_layout() {
+        let bidder = Pubkey::new_unique();
+        let mut bid: BidAccount = zeroed();
+        bid.auction_id = u64::MAX;
+        bid.bidder = bidder;
+        
+        let data = serialize(&bid);
+        assert_eq!(&data[BIDDER_OFFSET..BIDDER_OFFSET + 32], bidder.as_ref());
+    }
+}
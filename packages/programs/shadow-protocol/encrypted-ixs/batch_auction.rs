@@ -0,0 +1,91 @@
+use arcis_imports::*;
+
+#[encrypted]
+mod circuits {
+    use arcis_imports::*;
+
+    pub struct BatchOrderData {
+        pub trader_id: u128,
+        pub is_buy: bool,
+        pub price: u64,
+        pub size: u64,
+        pub base_deposited: u64,
+        pub quote_deposited: u64,
+    }
+
+    pub struct BatchFillResult {
+        pub clearing_price: u64,
+        pub matched_volume: u64,
+        pub fills: Vec<u64>,
+    }
+
+    #[instruction]
+    pub fn process_batch_auction(
+        orders: Enc<Shared, Vec<BatchOrderData>>,
+    ) -> Enc<Shared, BatchFillResult> {
+        let order_list = orders.to_arcis();
+
+        // An order only counts for the size its deposit can cover at its limit price
+        let mut sizes: Vec<u64> = Vec::new();
+        for order in order_list.iter() {
+            let covered = if order.is_buy {
+                if order.price > 0 { order.quote_deposited / order.price } else { 0 }
+            } else {
+                order.base_deposited
+            };
+            sizes.push(order.size.min(covered));
+        }
+
+        // Try every limit price as a candidate and keep the one matching the most volume
+        let mut clearing_price: u64 = 0;
+        let mut matched_volume: u64 = 0;
+
+        for candidate in order_list.iter() {
+            let price = candidate.price;
+            let mut demand: u64 = 0;
+            let mut supply: u64 = 0;
+
+            for (i, order) in order_list.iter().enumerate() {
+                // Buyers pay at most their limit; buyers also need enough quote at this price
+                let buy_size = if price > 0 { sizes[i].min(order.quote_deposited / price) } else { 0 };
+                if order.is_buy && order.price >= price {
+                    demand += buy_size;
+                }
+                if !order.is_buy && order.price <= price {
+                    supply += sizes[i];
+                }
+            }
+
+            let volume = demand.min(supply);
+            if volume > matched_volume {
+                matched_volume = volume;
+                clearing_price = price;
+            }
+        }
+
+        // Allocate the matched volume on each side in submission order
+        let mut buy_left = matched_volume;
+        let mut sell_left = matched_volume;
+        let mut fills: Vec<u64> = Vec::new();
+
+        for (i, order) in order_list.iter().enumerate() {
+            let mut fill: u64 = 0;
+            if order.is_buy && order.price >= clearing_price && clearing_price > 0 {
+                fill = sizes[i].min(order.quote_deposited / clearing_price).min(buy_left);
+                buy_left -= fill;
+            } else if !order.is_buy && order.price <= clearing_price {
+                fill = sizes[i].min(sell_left);
+                sell_left -= fill;
+            }
+            fills.push(fill);
+        }
+
+        let result = BatchFillResult {
+            clearing_price,
+            matched_volume,
+            fills,
+        };
+
+        orders.owner.from_arcis(result)
+    }
+}
@@ -29,10 +29,19 @@ mod circuits {
         let mut failed_count = 0u64;
         let mut total_volume = 0u64;
         let mut total_fees = 0u64;
+        let mut results = Vec::new();
         
         // Process each auction settlement
         for settlement in batch_data.auction_settlements.iter() {
-            if settlement.reserve_met && settlement.winner_id != 0 {
+            let succeeded = settlement.reserve_met && settlement.winner_id != 0;
+            results.push(SingleAuctionResult {
+                auction_id: settlement.auction_id,
+                winner_id: settlement.winner_id,
+                winning_amount: if succeeded { settlement.winning_amount } else { 0 },
+                reserve_met: succeeded,
+            });
+            
+            if succeeded {
                 successful_count += 1;
                 total_volume += settlement.winning_amount;
                 
@@ -53,6 +62,7 @@ mod circuits {
             total_volume,
             total_fees,
             computation_complete: true,
+            results,
         };
         
         batch.owner.from_arcis(result)
@@ -95,5 +105,14 @@ mod circuits {
         pub total_volume: u64,
         pub total_fees: u64,
         pub computation_complete: bool,
+        pub results: Vec<SingleAuctionResult>,
+    }
+
+    // Per-auction outcome applied on-chain by `apply_batch_settlement`
+    pub struct SingleAuctionResult {
+        pub auction_id: u64,
+        pub winner_id: u128,
+        pub winning_amount: u64,
+        pub reserve_met: bool,
     }
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+use arcis_imports::*;
+
+#[encrypted]
+mod circuits {
+    use arcis_imports::*;
+
+    pub struct MultiUnitBid {
+        pub bidder_id: u128,
+        pub price: u64,
+        pub quantity: u64,
+        pub collateral: u64,
+    }
+
+    pub struct MultiUnitAuctionData {
+        pub reserve_price: u64,
+        pub total_units: u64,
+    }
+
+    pub struct MultiUnitResult {
+        pub clearing_price: u64,
+        pub units_allocated: u64,
+        pub allocations: Vec<u64>,
+    }
+
+    #[instruction]
+    pub fn process_multi_unit_auction(
+        auction_data: Enc<Mxe, MultiUnitAuctionData>,
+        bids: Enc<Shared, Vec<MultiUnitBid>>,
+    ) -> Enc<Shared, MultiUnitResult> {
+        let auction = auction_data.to_arcis();
+        let bid_list = bids.to_arcis();
+
+        // A bid only counts for the quantity its collateral covers at its own price
+        let mut remaining: Vec<u64> = Vec::new();
+        for bid in bid_list.iter() {
+            let affordable = if bid.price > 0 { bid.collateral / bid.price } else { 0 };
+            let quantity = if bid.price >= auction.reserve_price {
+                bid.quantity.min(affordable)
+            } else {
+                0
+            };
+            remaining.push(quantity);
+        }
+
+        let mut allocations: Vec<u64> = Vec::new();
+        for _ in bid_list.iter() {
+            allocations.push(0);
+        }
+
+        // Fill supply from the highest price down; ties go to the earlier bid
+        let mut units_left = auction.total_units;
+        let mut clearing_price: u64 = 0;
+
+        for _ in bid_list.iter() {
+            let mut best_index = 0usize;
+            let mut best_price: u64 = 0;
+            let mut found = false;
+
+            for (i, bid) in bid_list.iter().enumerate() {
+                if remaining[i] > 0 && allocations[i] == 0 && bid.price > best_price {
+                    best_index = i;
+                    best_price = bid.price;
+                    found = true;
+                }
+            }
+
+            if found && units_left > 0 {
+                let filled = remaining[best_index].min(units_left);
+                allocations[best_index] = filled;
+                units_left -= filled;
+
+                // Uniform price: every winner pays the lowest accepted bid
+                clearing_price = best_price;
+            }
+        }
+
+        let result = MultiUnitResult {
+            clearing_price: clearing_price.max(auction.reserve_price),
+            units_allocated: auction.total_units - units_left,
+            allocations,
+        };
+
+        bids.owner.from_arcis(result)
+    }
+}
@@ -0,0 +1,59 @@
+use arcis_imports::*;
+
+#[encrypted]
+mod circuits {
+    use arcis_imports::*;
+
+    pub struct AskData {
+        pub auction_id: u64,
+        pub ask_amount: u64,
+        pub supplier_id: u128,
+    }
+
+    pub struct ReverseAuctionData {
+        pub ceiling_price: u64,
+        pub budget: u64,
+        pub second_price: bool,
+    }
+
+    #[instruction]
+    pub fn process_reverse_auction(
+        auction_data: Enc<Mxe, ReverseAuctionData>,
+        asks: Enc<Shared, Vec<AskData>>,
+    ) -> Enc<Shared, (u128, u64, bool)> { // (winner_id, winning_amount, ceiling_met)
+        let auction = auction_data.to_arcis();
+        let ask_list = asks.to_arcis();
+
+        // Asks above the hidden ceiling or the escrowed budget are ignored
+        let limit = auction.ceiling_price.min(auction.budget);
+
+        // Find lowest ask
+        let mut lowest_ask: u64 = u64::MAX;
+        let mut second_lowest: u64 = u64::MAX;
+        let mut winner_id: u128 = 0;
+
+        for ask in ask_list.iter() {
+            let eligible = ask.ask_amount > 0 && ask.ask_amount <= limit;
+            if eligible && ask.ask_amount < lowest_ask {
+                second_lowest = lowest_ask;
+                lowest_ask = ask.ask_amount;
+                winner_id = ask.supplier_id;
+            } else if eligible && ask.ask_amount < second_lowest {
+                second_lowest = ask.ask_amount;
+            }
+        }
+
+        let ceiling_met = winner_id != 0;
+
+        // Second-lowest pricing pays the runner-up ask, or the ceiling if unopposed
+        let winning_amount = if !ceiling_met {
+            0
+        } else if auction.second_price {
+            second_lowest.min(limit)
+        } else {
+            lowest_ask
+        };
+
+        asks.owner.from_arcis((winner_id, winning_amount, ceiling_met))
+    }
+}
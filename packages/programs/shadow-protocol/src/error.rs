@@ -103,4 +103,205 @@ pub enum ShadowProtocolError {
     
     #[msg("Price below minimum floor")]
     PriceBelowMinimumFloor,
+    
+    #[msg("Invalid unit count")]
+    InvalidUnitCount,
+    
+    #[msg("Allocated units exceed units offered")]
+    AllocationExceedsSupply,
+    
+    #[msg("Bid has already been settled")]
+    BidAlreadySettled,
+    
+    #[msg("Bid account does not belong to this auction")]
+    InvalidBidAccount,
+    
+    #[msg("Collateral mint does not match auction payment mint")]
+    InvalidPaymentMint,
+    
+    #[msg("Winning amount exceeds escrowed budget")]
+    BudgetExceeded,
+    
+    #[msg("Auction cannot be cancelled")]
+    CannotCancelAuction,
+    
+    #[msg("Bid is not eligible for a refund")]
+    RefundNotAvailable,
+    
+    #[msg("Invalid bundle composition")]
+    InvalidBundle,
+    
+    #[msg("Bundle account does not match manifest")]
+    BundleAccountMismatch,
+    
+    #[msg("Order must deposit base or quote tokens")]
+    EmptyOrderDeposit,
+    
+    #[msg("Order fill exceeds its deposit")]
+    FillExceedsDeposit,
+    
+    #[msg("Bought and sold quantities do not match")]
+    UnbalancedBatchFills,
+    
+    #[msg("Order has already been filled")]
+    OrderAlreadyFilled,
+    
+    #[msg("Auctions in a batch must share a creator")]
+    BatchCreatorMismatch,
+    
+    #[msg("Auction is already committed to a batch")]
+    AuctionAlreadyBatched,
+    
+    #[msg("Settlement account does not match the auction")]
+    SettlementAccountMismatch,
+    
+    #[msg("Batch has not finished settling")]
+    BatchNotFinalized,
+    
+    #[msg("Role has already been granted to this key")]
+    RoleAlreadyGranted,
+    
+    #[msg("Role is not held by this key")]
+    RoleNotGranted,
+    
+    #[msg("Maximum role grants exceeded")]
+    TooManyRoleGrants,
+    
+    #[msg("Action requires multisig approval")]
+    MultisigRequired,
+    
+    #[msg("Admin multisig is already configured")]
+    MultisigAlreadyConfigured,
+    
+    #[msg("Invalid multisig members or threshold")]
+    InvalidMultisigConfig,
+    
+    #[msg("Signer is not a multisig member")]
+    NotMultisigMember,
+    
+    #[msg("Member has already approved this proposal")]
+    ProposalAlreadyApproved,
+    
+    #[msg("Proposal has not reached the approval threshold")]
+    ProposalThresholdNotMet,
+    
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    
+    #[msg("No pending fee update")]
+    NoPendingFeeUpdate,
+    
+    #[msg("Fee update timelock not elapsed")]
+    FeeUpdateTimelockNotElapsed,
+    
+    #[msg("Timelock delay out of bounds")]
+    InvalidTimelockDelay,
+    
+    #[msg("Unknown pause flag")]
+    InvalidPauseFlags,
+    
+    #[msg("Auction is frozen")]
+    AuctionFrozen,
+    
+    #[msg("Fee vault balance is insufficient for the withdrawal")]
+    InsufficientTreasuryBalance,
+    
+    #[msg("Withdrawal amount must be greater than zero")]
+    InvalidWithdrawalAmount,
+    
+    #[msg("Bidders cannot refer their own bids")]
+    InvalidReferrer,
+    
+    #[msg("Referral fee exceeds the maximum")]
+    InvalidReferralFee,
+    
+    #[msg("Fee tiers must ascend by volume and not exceed the protocol fee")]
+    InvalidFeeTiers,
+    
+    #[msg("Settlement fees and royalties exceed the sale price")]
+    SettlementSplitsExceedPrice,
+    
+    #[msg("Invalid Metaplex metadata account")]
+    InvalidMetadataAccount,
+    
+    #[msg("Token account still holds funds and cannot be closed")]
+    AccountNotEmpty,
+    
+    #[msg("All bids must be refunded and closed before the auction can be closed")]
+    OpenBidsRemaining,
+    
+    #[msg("Bid must be settled or refunded before it can be closed")]
+    BidNotSettled,
+    
+    #[msg("Account is not a migratable program account")]
+    InvalidMigrationAccount,
+    
+    #[msg("Account is already at the current layout version")]
+    AccountAlreadyMigrated,
+    
+    #[msg("Bidder does not satisfy the auction's gate")]
+    BidderNotAllowed,
+    
+    #[msg("Auction gate cannot be changed once bidding has started")]
+    InvalidAuctionGate,
+    
+    #[msg("Wallet has reached the maximum number of open bids")]
+    TooManyActiveBids,
+    
+    #[msg("Bid bond must be paid to the seller's account in the auction's collateral mint")]
+    InvalidBidBondAccount,
+    
+    #[msg("Bid deposit exceeds the maximum")]
+    InvalidBidLimits,
+    
+    #[msg("Auction metadata field exceeds its maximum length")]
+    MetadataTooLong,
+    
+    #[msg("Auction metadata cannot change once bidding has started")]
+    MetadataLocked,
+    
+    #[msg("Asset mint is not a non-fungible token (supply 1, 0 decimals)")]
+    NotANonFungibleToken,
+    
+    #[msg("NFT is not a verified member of the required collection")]
+    CollectionNotVerified,
+    
+    #[msg("Missing or invalid accounts for a programmable NFT transfer")]
+    InvalidNftTransferAccounts,
+    
+    #[msg("Escrow account does not match the bid's collateral")]
+    InvalidEscrowAccount,
+    
+    #[msg("Price feed account is missing, unverified or for a different feed")]
+    InvalidPriceFeed,
+    
+    #[msg("Oracle price is too old")]
+    StalePrice,
+    
+    #[msg("Oracle price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    
+    #[msg("Invalid USD pricing parameters")]
+    InvalidUsdPricing,
+    
+    #[msg("Winning amount is below the USD reserve")]
+    ReserveNotMet,
+    
+    #[msg("Keeper bounty can only be funded with a positive amount while the auction is active")]
+    InvalidKeeperBounty,
+    
+    #[msg("Not every bid has a recorded allocation yet")]
+    AllocationIncomplete,
+    
+    #[msg("Auction account does not match the proposal")]
+    InvalidAuctionAccount,
+    
+    #[msg("NFT listings and USD-priced auctions must be settled individually")]
+    AuctionNotBatchable,
+    
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    
+    #[msg("Proposal needs an account that was not provided")]
+    ProposalAccountMissing,
 }
\ No newline at end of file
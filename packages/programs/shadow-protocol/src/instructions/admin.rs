@@ -9,11 +9,25 @@ pub fn initialize_protocol(ctx: Context<InitializeProtocol>) -> Result<()> {
     protocol.protocol_fee = 50; // 0.5% default fee
     protocol.fee_recipient = ctx.accounts.fee_recipient.key();
     protocol.paused = false;
+    protocol.pause_flags = 0;
     protocol.next_auction_id = 1; // Start auction IDs from 1
+    protocol.next_batch_id = 1;
     protocol.pending_authority = None;
     protocol.authority_transfer_timelock = None;
+    protocol.admin_multisig = None;
+    protocol.pending_protocol_fee = None;
+    protocol.pending_fee_recipient = None;
+    protocol.fee_update_timelock = None;
+    protocol.fee_update_delay = DEFAULT_FEE_UPDATE_DELAY;
+    protocol.referral_fee_bps = 0;
+    protocol.fee_tiers = Vec::new();
+    protocol.bid_deposit_lamports = DEFAULT_BID_DEPOSIT_LAMPORTS;
+    protocol.max_active_bids_per_wallet = DEFAULT_MAX_ACTIVE_BIDS_PER_WALLET;
+    protocol.pending_referral_fee_bps = None;
+    protocol.pending_fee_tiers = None;
+    protocol.version = PROTOCOL_STATE_VERSION;
     protocol.bump = ctx.bumps.protocol_state;
-    protocol.reserved = [0u8; 100]; // Reduced due to new fields
+    protocol.reserved = [0u8; 100];
     
     msg!("Shadow Protocol initialized");
     msg!("Authority: {}", protocol.authority);
@@ -23,42 +37,334 @@ pub fn initialize_protocol(ctx: Context<InitializeProtocol>) -> Result<()> {
     Ok(())
 }
 
-pub fn set_pause_state(ctx: Context<SetPauseState>, paused: bool) -> Result<()> {
-    let protocol = &mut ctx.accounts.protocol_state;
+/// Check that `signer` is the protocol authority or holds `role`
+pub(crate) fn require_role(
+    protocol: &ProtocolState,
+    roles: Option<&ProtocolRoles>,
+    role: Role,
+    signer: Pubkey,
+) -> Result<()> {
+    let has_role = roles
+        .map(|roles| roles.grants.iter().any(|grant| grant.role == role && grant.member == signer))
+        .unwrap_or(false);
     
     require!(
-        ctx.accounts.authority.key() == protocol.authority,
+        signer == protocol.authority || has_role,
         ShadowProtocolError::Unauthorized
     );
     
-    protocol.paused = paused;
-    
-    msg!("Protocol pause state updated: {}", paused);
+    Ok(())
+}
+
+/// Pause flag covering operations on auctions of the given type
+pub(crate) fn auction_type_pause_flag(auction_type: AuctionType) -> u16 {
+    match auction_type {
+        AuctionType::SealedBid => PAUSE_SEALED_BID,
+        AuctionType::Dutch => PAUSE_DUTCH,
+        AuctionType::Batch => PAUSE_BATCH,
+        AuctionType::MultiUnit => PAUSE_MULTI_UNIT,
+        AuctionType::Reverse => PAUSE_REVERSE,
+    }
+}
+
+/// Check that neither `operation` nor the auction type is paused. Refunds
+/// and cancellations never call this so funds can always be withdrawn.
+pub(crate) fn require_not_paused(
+    protocol: &ProtocolState,
+    operation: u16,
+    auction_type: AuctionType,
+) -> Result<()> {
+    require!(
+        protocol.pause_flags & (operation | auction_type_pause_flag(auction_type)) == 0,
+        ShadowProtocolError::ProtocolPaused
+    );
     
     Ok(())
 }
 
-pub fn update_protocol_fee(ctx: Context<UpdateProtocolFee>, new_fee: u16) -> Result<()> {
+/// Pause or resume every pausable operation at once
+pub fn set_pause_state(ctx: Context<SetPauseState>, paused: bool) -> Result<()> {
+    set_pause_flags(ctx, if paused { PAUSE_ALL } else { 0 })
+}
+
+/// Replace the protocol pause bitmask
+pub fn set_pause_flags(ctx: Context<SetPauseState>, pause_flags: u16) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol_state;
     
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::Pauser,
+        ctx.accounts.authority.key(),
+    )?;
+    
     require!(
-        ctx.accounts.authority.key() == protocol.authority,
-        ShadowProtocolError::Unauthorized
+        pause_flags & !PAUSE_ALL == 0,
+        ShadowProtocolError::InvalidPauseFlags
     );
     
+    // Pausing stays single-key for emergencies; unpausing needs the multisig
+    let previous_flags = protocol.pause_flags;
+    if previous_flags & !pause_flags != 0 {
+        require!(
+            protocol.admin_multisig.is_none(),
+            ShadowProtocolError::MultisigRequired
+        );
+    }
+    
+    protocol.pause_flags = pause_flags;
+    protocol.paused = pause_flags != 0;
+    
+    emit!(PauseFlagsUpdated {
+        previous_flags,
+        pause_flags,
+        updated_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Protocol pause flags updated: {:#06x}", pause_flags);
+    
+    Ok(())
+}
+
+/// Freeze or unfreeze a single auction. Frozen auctions reject bids and
+/// settlement but can still be cancelled and refunded.
+pub fn set_auction_frozen(ctx: Context<SetAuctionFrozen>, auction_id: u64, frozen: bool) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
+    let auction = &mut ctx.accounts.auction;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::Pauser,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    // Like unpausing, lifting a freeze needs the multisig once configured
+    if auction.frozen && !frozen {
+        require!(
+            protocol.admin_multisig.is_none(),
+            ShadowProtocolError::MultisigRequired
+        );
+    }
+    
+    auction.frozen = frozen;
+    
+    emit!(AuctionFreezeUpdated {
+        auction_id,
+        frozen,
+        updated_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Auction {} frozen: {}", auction_id, frozen);
+    
+    Ok(())
+}
+
+/// Queue a protocol fee change behind the fee update timelock
+pub(crate) fn queue_protocol_fee(protocol: &mut ProtocolState, new_fee: u16) -> Result<()> {
     require!(
         new_fee <= MAX_PROTOCOL_FEE,
         ShadowProtocolError::InvalidProtocolFee
     );
     
-    protocol.protocol_fee = new_fee;
+    protocol.pending_protocol_fee = Some(new_fee);
+    start_fee_update_timelock(protocol)
+}
+
+/// Queue a fee recipient change behind the fee update timelock
+pub(crate) fn queue_fee_recipient(protocol: &mut ProtocolState, new_recipient: Pubkey) -> Result<()> {
+    protocol.pending_fee_recipient = Some(new_recipient);
+    start_fee_update_timelock(protocol)
+}
+
+/// Queue a referral fee change behind the fee update timelock
+pub(crate) fn queue_referral_fee(protocol: &mut ProtocolState, referral_fee_bps: u16) -> Result<()> {
+    require!(
+        referral_fee_bps <= MAX_REFERRAL_FEE,
+        ShadowProtocolError::InvalidReferralFee
+    );
     
-    msg!("Protocol fee updated to {} basis points", new_fee);
+    protocol.pending_referral_fee_bps = Some(referral_fee_bps);
+    start_fee_update_timelock(protocol)
+}
+
+/// Queue a fee tier replacement behind the fee update timelock. Tiers must
+/// ascend by volume and may only discount the protocol fee.
+pub(crate) fn queue_fee_tiers(protocol: &mut ProtocolState, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    require!(
+        fee_tiers.len() <= MAX_FEE_TIERS,
+        ShadowProtocolError::InvalidFeeTiers
+    );
+    
+    for (i, tier) in fee_tiers.iter().enumerate() {
+        require!(
+            tier.fee_bps <= protocol.protocol_fee,
+            ShadowProtocolError::InvalidFeeTiers
+        );
+        if i > 0 {
+            require!(
+                tier.min_volume > fee_tiers[i - 1].min_volume,
+                ShadowProtocolError::InvalidFeeTiers
+            );
+        }
+    }
+    
+    protocol.pending_fee_tiers = Some(fee_tiers);
+    start_fee_update_timelock(protocol)
+}
+
+/// (Re)start the timelock covering every pending fee change
+fn start_fee_update_timelock(protocol: &mut ProtocolState) -> Result<()> {
+    let clock = Clock::get()?;
+    let effective_at = clock.unix_timestamp
+        .checked_add(protocol.fee_update_delay)
+        .ok_or(ShadowProtocolError::InvalidTimestamp)?;
+    
+    protocol.fee_update_timelock = Some(effective_at);
+    
+    emit!(FeeUpdateQueued {
+        pending_protocol_fee: protocol.pending_protocol_fee,
+        pending_fee_recipient: protocol.pending_fee_recipient,
+        pending_referral_fee_bps: protocol.pending_referral_fee_bps,
+        pending_fee_tiers: protocol.pending_fee_tiers.clone(),
+        effective_at,
+    });
+    
+    msg!("Fee update queued, effective at {}", effective_at);
     
     Ok(())
 }
 
-pub fn update_fee_recipient(ctx: Context<UpdateFeeRecipient>, new_recipient: Pubkey) -> Result<()> {
+/// Queue a protocol fee change (applied after the fee update delay)
+pub fn queue_protocol_fee_update(ctx: Context<QueueProtocolFeeUpdate>, new_fee: u16) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::FeeManager,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    queue_protocol_fee(protocol, new_fee)
+}
+
+/// Queue a fee recipient change (applied after the fee update delay)
+pub fn queue_fee_recipient_update(ctx: Context<QueueFeeRecipientUpdate>, new_recipient: Pubkey) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::FeeManager,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    queue_fee_recipient(protocol, new_recipient)
+}
+
+/// Apply queued fee changes once the timelock has elapsed (permissionless)
+pub fn apply_fee_update(ctx: Context<ApplyFeeUpdate>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    let effective_at = protocol.fee_update_timelock
+        .ok_or(ShadowProtocolError::NoPendingFeeUpdate)?;
+    
+    require!(
+        clock.unix_timestamp >= effective_at,
+        ShadowProtocolError::FeeUpdateTimelockNotElapsed
+    );
+    
+    if let Some(new_fee) = protocol.pending_protocol_fee.take() {
+        protocol.protocol_fee = new_fee;
+    }
+    
+    if let Some(new_recipient) = protocol.pending_fee_recipient.take() {
+        protocol.fee_recipient = new_recipient;
+    }
+    
+    if let Some(referral_fee_bps) = protocol.pending_referral_fee_bps.take() {
+        protocol.referral_fee_bps = referral_fee_bps;
+    }
+    
+    if let Some(fee_tiers) = protocol.pending_fee_tiers.take() {
+        protocol.fee_tiers = fee_tiers;
+    }
+    
+    protocol.fee_update_timelock = None;
+    
+    emit!(FeeUpdateApplied {
+        protocol_fee: protocol.protocol_fee,
+        fee_recipient: protocol.fee_recipient,
+        referral_fee_bps: protocol.referral_fee_bps,
+        fee_tiers: protocol.fee_tiers.clone(),
+    });
+    
+    msg!("Fee update applied: {} basis points to {}", protocol.protocol_fee, protocol.fee_recipient);
+    
+    Ok(())
+}
+
+/// Cancel queued fee changes
+pub fn cancel_fee_update(ctx: Context<CancelFeeUpdate>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::FeeManager,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    require!(
+        protocol.fee_update_timelock.is_some(),
+        ShadowProtocolError::NoPendingFeeUpdate
+    );
+    
+    protocol.pending_protocol_fee = None;
+    protocol.pending_fee_recipient = None;
+    protocol.pending_referral_fee_bps = None;
+    protocol.pending_fee_tiers = None;
+    protocol.fee_update_timelock = None;
+    
+    emit!(FeeUpdateCancelled {
+        cancelled_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Fee update cancelled");
+    
+    Ok(())
+}
+
+/// Change the delay applied to future fee updates
+pub(crate) fn change_fee_update_delay(protocol: &mut ProtocolState, new_delay: i64) -> Result<()> {
+    require!(
+        (MIN_FEE_UPDATE_DELAY..=MAX_FEE_UPDATE_DELAY).contains(&new_delay),
+        ShadowProtocolError::InvalidTimelockDelay
+    );
+    
+    protocol.fee_update_delay = new_delay;
+    
+    msg!("Fee update delay set to {} seconds", new_delay);
+    
+    Ok(())
+}
+
+/// Change the delay applied to future fee updates (admin only)
+pub fn set_fee_update_delay(ctx: Context<SetFeeUpdateDelay>, new_delay: i64) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol_state;
     
     require!(
@@ -66,13 +372,99 @@ pub fn update_fee_recipient(ctx: Context<UpdateFeeRecipient>, new_recipient: Pub
         ShadowProtocolError::Unauthorized
     );
     
-    protocol.fee_recipient = new_recipient;
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    change_fee_update_delay(protocol, new_delay)
+}
+
+/// Queue a referral fee change (applied after the fee update delay)
+pub fn queue_referral_fee_update(ctx: Context<UpdateFeeConfig>, referral_fee_bps: u16) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::FeeManager,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    queue_referral_fee(protocol, referral_fee_bps)
+}
+
+/// Queue a fee tier replacement (applied after the fee update delay)
+pub fn queue_fee_tiers_update(ctx: Context<UpdateFeeConfig>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::FeeManager,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    queue_fee_tiers(protocol, fee_tiers)
+}
+
+/// Change the anti-spam deposit and per-wallet open bid cap
+pub(crate) fn change_bid_limits(
+    protocol: &mut ProtocolState,
+    bid_deposit_lamports: u64,
+    max_active_bids_per_wallet: u32,
+) -> Result<()> {
+    require!(
+        bid_deposit_lamports <= MAX_BID_DEPOSIT_LAMPORTS,
+        ShadowProtocolError::InvalidBidLimits
+    );
+    
+    protocol.bid_deposit_lamports = bid_deposit_lamports;
+    protocol.max_active_bids_per_wallet = max_active_bids_per_wallet;
     
-    msg!("Fee recipient updated to {}", new_recipient);
+    emit!(BidLimitsUpdated {
+        bid_deposit_lamports,
+        max_active_bids_per_wallet,
+    });
     
     Ok(())
 }
 
+/// Set the anti-spam deposit and per-wallet open bid cap (admin only)
+pub fn set_bid_limits(
+    ctx: Context<SetBidLimits>,
+    bid_deposit_lamports: u64,
+    max_active_bids_per_wallet: u32,
+) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    
+    require!(
+        ctx.accounts.authority.key() == protocol.authority,
+        ShadowProtocolError::Unauthorized
+    );
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    change_bid_limits(protocol, bid_deposit_lamports, max_active_bids_per_wallet)
+}
+
 /// Initiate authority transfer (first step)
 pub fn initiate_authority_transfer(ctx: Context<InitiateAuthorityTransfer>, new_authority: Pubkey) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol_state;
@@ -83,52 +475,66 @@ pub fn initiate_authority_transfer(ctx: Context<InitiateAuthorityTransfer>, new_
         ShadowProtocolError::Unauthorized
     );
     
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
     require!(
         protocol.pending_authority.is_none(),
         ShadowProtocolError::AuthorityTransferPending
     );
     
+    let timelock_expires = clock.unix_timestamp + AUTHORITY_TRANSFER_TIMELOCK;
     protocol.pending_authority = Some(new_authority);
-    protocol.authority_transfer_timelock = Some(clock.unix_timestamp + AUTHORITY_TRANSFER_TIMELOCK);
+    protocol.authority_transfer_timelock = Some(timelock_expires);
+    
+    emit!(AuthorityTransferInitiated {
+        current_authority: protocol.authority,
+        pending_authority: new_authority,
+        timelock_expires,
+    });
     
     msg!("Authority transfer initiated. New authority: {}, Timelock until: {}", 
          new_authority, 
-         clock.unix_timestamp + AUTHORITY_TRANSFER_TIMELOCK);
+         timelock_expires);
     
     Ok(())
 }
 
-/// Complete authority transfer (second step, after timelock)
-pub fn complete_authority_transfer(ctx: Context<CompleteAuthorityTransfer>) -> Result<()> {
+/// Accept authority transfer (second step, signed by the pending authority
+/// after the timelock)
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol_state;
     let clock = Clock::get()?;
     
-    require!(
-        ctx.accounts.authority.key() == protocol.authority,
-        ShadowProtocolError::Unauthorized
-    );
+    let new_authority = protocol.pending_authority
+        .ok_or(ShadowProtocolError::NoPendingAuthorityTransfer)?;
     
+    // The incoming key must prove control by signing
     require!(
-        protocol.pending_authority.is_some(),
-        ShadowProtocolError::NoPendingAuthorityTransfer
-    );
-    
-    require!(
-        protocol.authority_transfer_timelock.is_some(),
-        ShadowProtocolError::NoPendingAuthorityTransfer
+        ctx.accounts.pending_authority.key() == new_authority,
+        ShadowProtocolError::Unauthorized
     );
     
-    let timelock_expires = protocol.authority_transfer_timelock.unwrap();
+    let timelock_expires = protocol.authority_transfer_timelock
+        .ok_or(ShadowProtocolError::NoPendingAuthorityTransfer)?;
     require!(
         clock.unix_timestamp >= timelock_expires,
         ShadowProtocolError::AuthorityTransferTimelockNotElapsed
     );
     
-    let new_authority = protocol.pending_authority.unwrap();
+    let previous_authority = protocol.authority;
     protocol.authority = new_authority;
     protocol.pending_authority = None;
     protocol.authority_transfer_timelock = None;
     
+    emit!(AuthorityTransferCompleted {
+        previous_authority,
+        new_authority,
+    });
+    
     msg!("Authority transfer completed. New authority: {}", new_authority);
     
     Ok(())
@@ -148,29 +554,134 @@ pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Resul
         ShadowProtocolError::NoPendingAuthorityTransfer
     );
     
-    protocol.pending_authority = None;
+    let pending_authority = protocol.pending_authority.take();
     protocol.authority_transfer_timelock = None;
     
+    emit!(AuthorityTransferCancelled {
+        pending_authority: pending_authority.unwrap_or_default(),
+        cancelled_by: ctx.accounts.authority.key(),
+    });
+    
     msg!("Authority transfer cancelled");
     
     Ok(())
 }
 
-pub fn transfer_authority(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
-    let protocol = &mut ctx.accounts.protocol_state;
+/// Create the roles account holding delegated admin roles
+pub fn initialize_roles(ctx: Context<InitializeRoles>) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
     
     require!(
         ctx.accounts.authority.key() == protocol.authority,
         ShadowProtocolError::Unauthorized
     );
     
-    protocol.authority = new_authority;
+    // A multisig creates the roles account with its first GrantRole proposal
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    let roles = &mut ctx.accounts.roles;
+    roles.grants = Vec::new();
+    roles.bump = ctx.bumps.roles;
+    
+    msg!("Protocol roles initialized");
+    
+    Ok(())
+}
+
+/// Add a role grant to the roles account
+pub(crate) fn add_role_grant(
+    roles: &mut ProtocolRoles,
+    role: Role,
+    member: Pubkey,
+    granted_by: Pubkey,
+) -> Result<()> {
+    require!(
+        !roles.grants.iter().any(|grant| grant.role == role && grant.member == member),
+        ShadowProtocolError::RoleAlreadyGranted
+    );
+    
+    require!(
+        roles.grants.len() < MAX_ROLE_GRANTS,
+        ShadowProtocolError::TooManyRoleGrants
+    );
+    
+    roles.grants.push(RoleGrant { role, member });
     
-    msg!("Authority transferred to {}", new_authority);
+    emit!(RoleGranted {
+        role,
+        member,
+        granted_by,
+    });
+    
+    msg!("Role {:?} granted to {}", role, member);
+    
+    Ok(())
+}
+
+/// Remove a role grant from the roles account
+pub(crate) fn remove_role_grant(
+    roles: &mut ProtocolRoles,
+    role: Role,
+    member: Pubkey,
+    revoked_by: Pubkey,
+) -> Result<()> {
+    let index = roles.grants
+        .iter()
+        .position(|grant| grant.role == role && grant.member == member)
+        .ok_or(ShadowProtocolError::RoleNotGranted)?;
+    
+    roles.grants.remove(index);
+    
+    emit!(RoleRevoked {
+        role,
+        member,
+        revoked_by,
+    });
+    
+    msg!("Role {:?} revoked from {}", role, member);
     
     Ok(())
 }
 
+/// Grant a role to a key (super-admin only)
+pub fn grant_role(ctx: Context<ManageRole>, role: Role, member: Pubkey) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
+    
+    require!(
+        ctx.accounts.authority.key() == protocol.authority,
+        ShadowProtocolError::Unauthorized
+    );
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    add_role_grant(&mut ctx.accounts.roles, role, member, ctx.accounts.authority.key())
+}
+
+/// Revoke a role from a key (super-admin only)
+pub fn revoke_role(ctx: Context<ManageRole>, role: Role, member: Pubkey) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
+    
+    require!(
+        ctx.accounts.authority.key() == protocol.authority,
+        ShadowProtocolError::Unauthorized
+    );
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    remove_role_grant(&mut ctx.accounts.roles, role, member, ctx.accounts.authority.key())
+}
+
 #[derive(Accounts)]
 pub struct InitializeProtocol<'info> {
     #[account(mut)]
@@ -201,10 +712,43 @@ pub struct SetPauseState<'info> {
         bump = protocol_state.bump
     )]
     pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct SetAuctionFrozen<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateProtocolFee<'info> {
+pub struct QueueProtocolFeeUpdate<'info> {
     pub authority: Signer<'info>,
     
     #[account(
@@ -213,10 +757,17 @@ pub struct UpdateProtocolFee<'info> {
         bump = protocol_state.bump
     )]
     pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateFeeRecipient<'info> {
+pub struct QueueFeeRecipientUpdate<'info> {
     pub authority: Signer<'info>,
     
     #[account(
@@ -228,10 +779,17 @@ pub struct UpdateFeeRecipient<'info> {
     
     /// CHECK: New fee recipient account
     pub new_recipient: AccountInfo<'info>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
 }
 
 #[derive(Accounts)]
-pub struct TransferAuthority<'info> {
+pub struct InitiateAuthorityTransfer<'info> {
     pub authority: Signer<'info>,
     
     #[account(
@@ -246,7 +804,19 @@ pub struct TransferAuthority<'info> {
 }
 
 #[derive(Accounts)]
-pub struct InitiateAuthorityTransfer<'info> {
+pub struct AcceptAuthority<'info> {
+    pub pending_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
     pub authority: Signer<'info>,
     
     #[account(
@@ -255,15 +825,51 @@ pub struct InitiateAuthorityTransfer<'info> {
         bump = protocol_state.bump
     )]
     pub protocol_state: Account<'info, ProtocolState>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRoles<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
     
-    /// CHECK: New authority account
-    pub new_authority: AccountInfo<'info>,
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProtocolRoles::INIT_SPACE,
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Account<'info, ProtocolRoles>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CompleteAuthorityTransfer<'info> {
+pub struct ManageRole<'info> {
     pub authority: Signer<'info>,
     
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Account<'info, ProtocolRoles>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyFeeUpdate<'info> {
     #[account(
         mut,
         seeds = [PROTOCOL_SEED],
@@ -273,7 +879,57 @@ pub struct CompleteAuthorityTransfer<'info> {
 }
 
 #[derive(Accounts)]
-pub struct CancelAuthorityTransfer<'info> {
+pub struct CancelFeeUpdate<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateFeeConfig<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+}
+
+#[derive(Accounts)]
+pub struct SetBidLimits<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeUpdateDelay<'info> {
     pub authority: Signer<'info>,
     
     #[account(
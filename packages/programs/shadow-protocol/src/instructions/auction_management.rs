@@ -2,21 +2,55 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
 use crate::state::*;
 use crate::error::ShadowProtocolError;
+use super::admin::require_not_paused;
+use super::metadata::{verify_nft_listing, transfer_programmable_nft, NftTransferAccounts};
 
-pub fn create_sealed_auction(
-    ctx: Context<CreateSealedAuction>,
+/// Current position on an auction's clock: the unix timestamp, or the slot
+/// for slot-timed auctions
+pub(crate) fn current_auction_time(timing_mode: TimingMode, clock: &Clock) -> i64 {
+    match timing_mode {
+        TimingMode::Timestamp => clock.unix_timestamp,
+        TimingMode::Slot => clock.slot as i64,
+    }
+}
+
+fn max_auction_duration(timing_mode: TimingMode) -> i64 {
+    match timing_mode {
+        TimingMode::Timestamp => MAX_AUCTION_DURATION,
+        TimingMode::Slot => MAX_AUCTION_DURATION_SLOTS,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_sealed_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateSealedAuction<'info>>,
     asset_mint: Pubkey,
     asset_amount: u64,
     duration: u64,
     minimum_bid: u64,
     reserve_price_encrypted: [u8; 32],
     reserve_price_nonce: u128,
+    nft: Option<NftOptions>,
+    timing_mode: TimingMode,
 ) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol_state;
-    require!(!protocol.paused, ShadowProtocolError::ProtocolPaused);
+    require_not_paused(protocol, PAUSE_CREATE, AuctionType::SealedBid)?;
     
     require!(asset_amount > 0, ShadowProtocolError::InvalidAssetAmount);
     
+    // NFT listings must be a single token backed by Metaplex metadata
+    let (nft_listing, nft_royalty_bps) = nft
+        .as_ref()
+        .map(|options| verify_nft_listing(
+            &ctx.accounts.asset_mint,
+            asset_amount,
+            ctx.accounts.asset_metadata.as_ref(),
+            options,
+            protocol.protocol_fee.saturating_add(protocol.referral_fee_bps),
+        ))
+        .transpose()?
+        .unzip();
+    
     // Validate creator has sufficient assets
     require!(
         ctx.accounts.creator_asset_account.amount >= asset_amount,
@@ -31,12 +65,13 @@ pub fn create_sealed_auction(
         .checked_add(1)
         .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
     
+    // Slot-timed auctions count duration in slots rather than seconds
     let clock = Clock::get()?;
-    let start_time = clock.unix_timestamp;
+    let start_time = current_auction_time(timing_mode, &clock);
     let end_time = start_time + duration as i64;
     
     require!(
-        duration as i64 <= MAX_AUCTION_DURATION,
+        duration as i64 <= max_auction_duration(timing_mode),
         ShadowProtocolError::AuctionDurationTooLong
     );
     
@@ -62,20 +97,66 @@ pub fn create_sealed_auction(
     auction.settled_at = None;
     auction.mpc_verification_hash = None;
     auction.settlement_authorized = false;
+    auction.total_units = 1;
+    auction.units_allocated = 0;
+    auction.clearing_price = 0;
+    auction.bids_settled = 0;
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.pricing_rule = PricingRule::SecondPrice;
+    auction.budget = 0;
+    auction.bundle = None;
+    auction.batch = None;
+    auction.protocol_fee_bps = protocol.protocol_fee;
+    auction.referral_fee_bps = protocol.referral_fee_bps;
+    auction.frozen = false;
+    auction.bids_closed = 0;
+    auction.gate = None;
+    auction.bid_bond = 0;
+    auction.nft = nft_listing;
+    auction.usd_pricing = None;
+    auction.timing_mode = timing_mode;
+    auction.keeper_bounty = 0;
+    auction.keeper = None;
+    auction.allocations_recorded = 0;
+    auction.nft_royalty_bps = nft_royalty_bps;
+    auction.version = AUCTION_ACCOUNT_VERSION;
     auction.bump = ctx.bumps.auction;
     
     // Transfer exact asset amount to vault
-    transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.creator_asset_account.to_account_info(),
-                to: ctx.accounts.asset_vault.to_account_info(),
+    if nft_listing.is_some_and(|listing| listing.programmable) {
+        let metadata = ctx.accounts.asset_metadata
+            .as_ref()
+            .ok_or(ShadowProtocolError::InvalidMetadataAccount)?;
+        transfer_programmable_nft(
+            NftTransferAccounts {
+                token: ctx.accounts.creator_asset_account.to_account_info(),
+                token_owner: ctx.accounts.creator.to_account_info(),
+                destination: ctx.accounts.asset_vault.to_account_info(),
+                destination_owner: auction.to_account_info(),
+                mint: ctx.accounts.asset_mint.to_account_info(),
+                metadata: metadata.to_account_info(),
                 authority: ctx.accounts.creator.to_account_info(),
+                payer: ctx.accounts.creator.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                extra: ctx.remaining_accounts,
             },
-        ),
-        asset_amount,
-    )?;
+            asset_amount,
+            &[],
+        )?;
+    } else {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_asset_account.to_account_info(),
+                    to: ctx.accounts.asset_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            asset_amount,
+        )?;
+    }
     
     emit!(AuctionCreated {
         auction_id,
@@ -85,13 +166,24 @@ pub fn create_sealed_auction(
         start_time,
         end_time,
         minimum_bid,
+        timing_mode,
     });
     
+    if let Some(listing) = nft_listing {
+        emit!(NftAuctionCreated {
+            auction_id,
+            mint: ctx.accounts.asset_mint.key(),
+            collection: listing.collection,
+            programmable: listing.programmable,
+        });
+    }
+    
     Ok(())
 }
 
-pub fn create_dutch_auction(
-    ctx: Context<CreateDutchAuction>,
+#[allow(clippy::too_many_arguments)]
+pub fn create_dutch_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateDutchAuction<'info>>,
     asset_mint: Pubkey,
     asset_amount: u64,
     starting_price: u64,
@@ -100,12 +192,27 @@ pub fn create_dutch_auction(
     duration: u64,
     reserve_price_encrypted: [u8; 32],
     reserve_price_nonce: u128,
+    nft: Option<NftOptions>,
+    timing_mode: TimingMode,
 ) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol_state;
-    require!(!protocol.paused, ShadowProtocolError::ProtocolPaused);
+    require_not_paused(protocol, PAUSE_CREATE, AuctionType::Dutch)?;
     
     require!(asset_amount > 0, ShadowProtocolError::InvalidAssetAmount);
     
+    // NFT listings must be a single token backed by Metaplex metadata
+    let (nft_listing, nft_royalty_bps) = nft
+        .as_ref()
+        .map(|options| verify_nft_listing(
+            &ctx.accounts.asset_mint,
+            asset_amount,
+            ctx.accounts.asset_metadata.as_ref(),
+            options,
+            protocol.protocol_fee.saturating_add(protocol.referral_fee_bps),
+        ))
+        .transpose()?
+        .unzip();
+    
     // Validate creator has sufficient assets
     require!(
         ctx.accounts.creator_asset_account.amount >= asset_amount,
@@ -131,12 +238,13 @@ pub fn create_dutch_auction(
         .checked_add(1)
         .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
     
+    // Slot-timed auctions count duration in slots rather than seconds
     let clock = Clock::get()?;
-    let start_time = clock.unix_timestamp;
+    let start_time = current_auction_time(timing_mode, &clock);
     let end_time = start_time + duration as i64;
     
     require!(
-        duration as i64 <= MAX_AUCTION_DURATION,
+        duration as i64 <= max_auction_duration(timing_mode),
         ShadowProtocolError::AuctionDurationTooLong
     );
     
@@ -162,9 +270,187 @@ pub fn create_dutch_auction(
     auction.settled_at = None;
     auction.mpc_verification_hash = None;
     auction.settlement_authorized = false;
+    auction.total_units = 1;
+    auction.units_allocated = 0;
+    auction.clearing_price = 0;
+    auction.bids_settled = 0;
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.pricing_rule = PricingRule::FirstPrice;
+    auction.budget = 0;
+    auction.bundle = None;
+    auction.batch = None;
+    auction.protocol_fee_bps = protocol.protocol_fee;
+    auction.referral_fee_bps = protocol.referral_fee_bps;
+    auction.frozen = false;
+    auction.bids_closed = 0;
+    auction.gate = None;
+    auction.bid_bond = 0;
+    auction.nft = nft_listing;
+    auction.usd_pricing = None;
+    auction.timing_mode = timing_mode;
+    auction.keeper_bounty = 0;
+    auction.keeper = None;
+    auction.allocations_recorded = 0;
+    auction.nft_royalty_bps = nft_royalty_bps;
+    auction.version = AUCTION_ACCOUNT_VERSION;
     auction.bump = ctx.bumps.auction;
     
     // Transfer exact asset amount to vault
+    if nft_listing.is_some_and(|listing| listing.programmable) {
+        let metadata = ctx.accounts.asset_metadata
+            .as_ref()
+            .ok_or(ShadowProtocolError::InvalidMetadataAccount)?;
+        transfer_programmable_nft(
+            NftTransferAccounts {
+                token: ctx.accounts.creator_asset_account.to_account_info(),
+                token_owner: ctx.accounts.creator.to_account_info(),
+                destination: ctx.accounts.asset_vault.to_account_info(),
+                destination_owner: auction.to_account_info(),
+                mint: ctx.accounts.asset_mint.to_account_info(),
+                metadata: metadata.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+                payer: ctx.accounts.creator.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                extra: ctx.remaining_accounts,
+            },
+            asset_amount,
+            &[],
+        )?;
+    } else {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator_asset_account.to_account_info(),
+                    to: ctx.accounts.asset_vault.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            asset_amount,
+        )?;
+    }
+    
+    emit!(AuctionCreated {
+        auction_id,
+        creator: ctx.accounts.creator.key(),
+        asset_mint,
+        auction_type: AuctionType::Dutch,
+        start_time,
+        end_time,
+        minimum_bid: 0,
+        timing_mode,
+    });
+    
+    if let Some(listing) = nft_listing {
+        emit!(NftAuctionCreated {
+            auction_id,
+            mint: ctx.accounts.asset_mint.key(),
+            collection: listing.collection,
+            programmable: listing.programmable,
+        });
+    }
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_multi_unit_auction(
+    ctx: Context<CreateMultiUnitAuction>,
+    asset_mint: Pubkey,
+    total_units: u64,
+    unit_size: u64,
+    duration: u64,
+    minimum_bid: u64,
+    reserve_price_encrypted: [u8; 32],
+    reserve_price_nonce: u128,
+) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    require_not_paused(protocol, PAUSE_CREATE, AuctionType::MultiUnit)?;
+    
+    require!(
+        total_units > 0 && total_units <= MAX_UNITS_PER_AUCTION,
+        ShadowProtocolError::InvalidUnitCount
+    );
+    
+    require!(unit_size > 0, ShadowProtocolError::InvalidAssetAmount);
+    
+    // Total asset amount is the number of units times the base amount per unit
+    let asset_amount = total_units
+        .checked_mul(unit_size)
+        .ok_or(ShadowProtocolError::InvalidAssetAmount)?;
+    
+    // Validate creator has sufficient assets
+    require!(
+        ctx.accounts.creator_asset_account.amount >= asset_amount,
+        ShadowProtocolError::InsufficientFunds
+    );
+    
+    // Get the auction ID from protocol state
+    let auction_id = protocol.next_auction_id;
+    
+    // Increment next auction ID with overflow check
+    protocol.next_auction_id = protocol.next_auction_id
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    let clock = Clock::get()?;
+    let start_time = clock.unix_timestamp;
+    let end_time = start_time + duration as i64;
+    
+    require!(
+        duration as i64 <= MAX_AUCTION_DURATION,
+        ShadowProtocolError::AuctionDurationTooLong
+    );
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.auction_id = auction_id;
+    auction.creator = ctx.accounts.creator.key();
+    auction.asset_mint = asset_mint;
+    auction.asset_vault = ctx.accounts.asset_vault.key();
+    auction.asset_amount = asset_amount;
+    auction.auction_type = AuctionType::MultiUnit;
+    auction.status = AuctionStatus::Active;
+    auction.start_time = start_time;
+    auction.end_time = end_time;
+    auction.minimum_bid = minimum_bid;
+    auction.minimum_price_floor = 0;
+    auction.reserve_price_encrypted = reserve_price_encrypted;
+    auction.reserve_price_nonce = reserve_price_nonce;
+    auction.current_price = 0;
+    auction.price_decrease_rate = 0;
+    auction.bid_count = 0;
+    auction.winner = None;
+    auction.winning_amount = 0;
+    auction.settled_at = None;
+    auction.mpc_verification_hash = None;
+    auction.settlement_authorized = false;
+    auction.total_units = total_units;
+    auction.units_allocated = 0;
+    auction.clearing_price = 0;
+    auction.bids_settled = 0;
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.pricing_rule = PricingRule::FirstPrice;
+    auction.budget = 0;
+    auction.bundle = None;
+    auction.batch = None;
+    auction.protocol_fee_bps = protocol.protocol_fee;
+    auction.referral_fee_bps = protocol.referral_fee_bps;
+    auction.frozen = false;
+    auction.bids_closed = 0;
+    auction.gate = None;
+    auction.bid_bond = 0;
+    auction.nft = None;
+    auction.usd_pricing = None;
+    auction.timing_mode = TimingMode::Timestamp;
+    auction.keeper_bounty = 0;
+    auction.keeper = None;
+    auction.allocations_recorded = 0;
+    auction.nft_royalty_bps = None;
+    auction.version = AUCTION_ACCOUNT_VERSION;
+    auction.bump = ctx.bumps.auction;
+    
+    // Transfer all units to vault
     transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -181,10 +467,235 @@ pub fn create_dutch_auction(
         auction_id,
         creator: ctx.accounts.creator.key(),
         asset_mint,
-        auction_type: AuctionType::Dutch,
+        auction_type: AuctionType::MultiUnit,
+        start_time,
+        end_time,
+        minimum_bid,
+        timing_mode: TimingMode::Timestamp,
+    });
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_reverse_auction(
+    ctx: Context<CreateReverseAuction>,
+    asset_mint: Pubkey,
+    asset_amount: u64,
+    budget: u64,
+    duration: u64,
+    pricing_rule: PricingRule,
+    ceiling_price_encrypted: [u8; 32],
+    ceiling_price_nonce: u128,
+) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    require_not_paused(protocol, PAUSE_CREATE, AuctionType::Reverse)?;
+    
+    require!(asset_amount > 0, ShadowProtocolError::InvalidAssetAmount);
+    
+    require!(budget > 0, ShadowProtocolError::InvalidReservePrice);
+    
+    // Validate buyer can fund the budget
+    require!(
+        ctx.accounts.creator_payment_account.amount >= budget,
+        ShadowProtocolError::InsufficientFunds
+    );
+    
+    // Get the auction ID from protocol state
+    let auction_id = protocol.next_auction_id;
+    
+    // Increment next auction ID with overflow check
+    protocol.next_auction_id = protocol.next_auction_id
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    let clock = Clock::get()?;
+    let start_time = clock.unix_timestamp;
+    let end_time = start_time + duration as i64;
+    
+    require!(
+        duration as i64 <= MAX_AUCTION_DURATION,
+        ShadowProtocolError::AuctionDurationTooLong
+    );
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.auction_id = auction_id;
+    auction.creator = ctx.accounts.creator.key();
+    auction.asset_mint = asset_mint;
+    auction.asset_vault = ctx.accounts.budget_vault.key();
+    auction.asset_amount = asset_amount;
+    auction.auction_type = AuctionType::Reverse;
+    auction.status = AuctionStatus::Active;
+    auction.start_time = start_time;
+    auction.end_time = end_time;
+    auction.minimum_bid = 0;
+    auction.minimum_price_floor = 0;
+    // The hidden reserve acts as a price ceiling for asks
+    auction.reserve_price_encrypted = ceiling_price_encrypted;
+    auction.reserve_price_nonce = ceiling_price_nonce;
+    auction.current_price = 0;
+    auction.price_decrease_rate = 0;
+    auction.bid_count = 0;
+    auction.winner = None;
+    auction.winning_amount = 0;
+    auction.settled_at = None;
+    auction.mpc_verification_hash = None;
+    auction.settlement_authorized = false;
+    auction.total_units = 1;
+    auction.units_allocated = 0;
+    auction.clearing_price = 0;
+    auction.bids_settled = 0;
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.pricing_rule = pricing_rule;
+    auction.budget = budget;
+    auction.bundle = None;
+    auction.batch = None;
+    auction.protocol_fee_bps = protocol.protocol_fee;
+    auction.referral_fee_bps = protocol.referral_fee_bps;
+    auction.frozen = false;
+    auction.bids_closed = 0;
+    auction.gate = None;
+    auction.bid_bond = 0;
+    auction.nft = None;
+    auction.usd_pricing = None;
+    auction.timing_mode = TimingMode::Timestamp;
+    auction.keeper_bounty = 0;
+    auction.keeper = None;
+    auction.allocations_recorded = 0;
+    auction.nft_royalty_bps = None;
+    auction.version = AUCTION_ACCOUNT_VERSION;
+    auction.bump = ctx.bumps.auction;
+    
+    // Escrow the buyer's budget in the vault
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator_payment_account.to_account_info(),
+                to: ctx.accounts.budget_vault.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+            },
+        ),
+        budget,
+    )?;
+    
+    emit!(AuctionCreated {
+        auction_id,
+        creator: ctx.accounts.creator.key(),
+        asset_mint,
+        auction_type: AuctionType::Reverse,
         start_time,
         end_time,
         minimum_bid: 0,
+        timing_mode: TimingMode::Timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Check that `signer` may cancel the auction. The creator may cancel while
+/// no bids exist; the protocol authority may cancel an ended auction whose
+/// settlement computation produced no winner.
+pub(crate) fn validate_cancellation(
+    auction: &AuctionAccount,
+    protocol: &ProtocolState,
+    signer: Pubkey,
+) -> Result<()> {
+    require!(
+        auction.status == AuctionStatus::Active || auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::CannotCancelAuction
+    );
+    
+    // A Dutch auction with a winner has already taken payment
+    require!(
+        auction.winner.is_none() && !auction.settlement_authorized,
+        ShadowProtocolError::CannotCancelAuction
+    );
+    
+    // Batched auctions are resolved by their batch
+    require!(auction.batch.is_none(), ShadowProtocolError::AuctionAlreadyBatched);
+    
+    let is_creator_withdrawal = signer == auction.creator && auction.bid_count == 0;
+    let is_authority_cancel = signer == protocol.authority
+        && auction.status == AuctionStatus::Ended;
+    require!(
+        is_creator_withdrawal || is_authority_cancel,
+        ShadowProtocolError::Unauthorized
+    );
+    
+    Ok(())
+}
+
+/// Cancel an auction and return the vault contents to its creator
+pub fn cancel_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelAuction<'info>>,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    let signer = ctx.accounts.authority.key();
+    
+    validate_cancellation(auction, protocol, signer)?;
+    
+    require!(auction.bundle.is_none(), ShadowProtocolError::InvalidAuctionType);
+    
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let signer_seeds = &[&auction_seeds[..]];
+    
+    let vault_amount = ctx.accounts.asset_vault.amount;
+    if vault_amount > 0 && auction.nft.is_some_and(|listing| listing.programmable) {
+        let (Some(creator), Some(asset_mint), Some(metadata)) = (
+            &ctx.accounts.creator,
+            &ctx.accounts.asset_mint,
+            &ctx.accounts.asset_metadata,
+        ) else {
+            return err!(ShadowProtocolError::InvalidNftTransferAccounts);
+        };
+        
+        transfer_programmable_nft(
+            NftTransferAccounts {
+                token: ctx.accounts.asset_vault.to_account_info(),
+                token_owner: auction.to_account_info(),
+                destination: ctx.accounts.creator_refund_account.to_account_info(),
+                destination_owner: creator.to_account_info(),
+                mint: asset_mint.to_account_info(),
+                metadata: metadata.to_account_info(),
+                authority: auction.to_account_info(),
+                payer: ctx.accounts.authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                extra: ctx.remaining_accounts,
+            },
+            vault_amount,
+            signer_seeds,
+        )?;
+    } else if vault_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.asset_vault.to_account_info(),
+                    to: ctx.accounts.creator_refund_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            vault_amount,
+        )?;
+    }
+    
+    let clock = Clock::get()?;
+    auction.status = AuctionStatus::Cancelled;
+    
+    emit!(AuctionCancelled {
+        auction_id,
+        cancelled_by: signer,
+        cancelled_at: clock.unix_timestamp,
     });
     
     Ok(())
@@ -213,6 +724,9 @@ pub struct CreateSealedAuction<'info> {
     
     pub asset_mint: Account<'info, Mint>,
     
+    /// Mint bids are paid in (the native mint for SOL-denominated auctions)
+    pub payment_mint: Account<'info, Mint>,
+    
     #[account(
         init,
         payer = creator,
@@ -230,6 +744,11 @@ pub struct CreateSealedAuction<'info> {
     )]
     pub creator_asset_account: Account<'info, TokenAccount>,
     
+    /// Metaplex metadata for the asset mint (required for NFT listings)
+    /// CHECK: Owner, address and layout are validated in the handler
+    #[account(mut)]
+    pub asset_metadata: Option<UncheckedAccount<'info>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -257,6 +776,9 @@ pub struct CreateDutchAuction<'info> {
     
     pub asset_mint: Account<'info, Mint>,
     
+    /// Mint bids are paid in (the native mint for SOL-denominated auctions)
+    pub payment_mint: Account<'info, Mint>,
+    
     #[account(
         init,
         payer = creator,
@@ -274,6 +796,160 @@ pub struct CreateDutchAuction<'info> {
     )]
     pub creator_asset_account: Account<'info, TokenAccount>,
     
+    /// Metaplex metadata for the asset mint (required for NFT listings)
+    /// CHECK: Owner, address and layout are validated in the handler
+    #[account(mut)]
+    pub asset_metadata: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultiUnitAuction<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AuctionAccount::INIT_SPACE,
+        seeds = [AUCTION_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    pub asset_mint: Account<'info, Mint>,
+    
+    /// Mint bidders must use for collateral and payment
+    pub payment_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = creator,
+        token::mint = asset_mint,
+        token::authority = auction,
+        seeds = [ASSET_VAULT_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = asset_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_asset_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateReverseAuction<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AuctionAccount::INIT_SPACE,
+        seeds = [AUCTION_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Mint of the asset being procured
+    pub asset_mint: Account<'info, Mint>,
+    
+    /// Mint the budget is denominated in
+    pub payment_mint: Account<'info, Mint>,
+    
+    /// Vault holding the buyer's budget
+    #[account(
+        init,
+        payer = creator,
+        token::mint = payment_mint,
+        token::authority = auction,
+        seeds = [BUDGET_VAULT_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub budget_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_payment_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CancelAuction<'info> {
+    /// Auction creator or protocol authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Vault held by the auction (the budget vault for reverse auctions)
+    #[account(
+        mut,
+        address = auction.asset_vault
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+    
+    /// Creator's account receiving the vault contents
+    #[account(
+        mut,
+        token::mint = asset_vault.mint,
+        token::authority = auction.creator
+    )]
+    pub creator_refund_account: Account<'info, TokenAccount>,
+    
+    /// Auction creator (required to return a programmable NFT)
+    /// CHECK: Address is constrained to the auction creator
+    #[account(address = auction.creator)]
+    pub creator: Option<UncheckedAccount<'info>>,
+    
+    /// Asset mint (required to return a programmable NFT)
+    #[account(address = auction.asset_mint)]
+    pub asset_mint: Option<Account<'info, Mint>>,
+    
+    /// Metaplex metadata for the asset mint (required to return a programmable NFT)
+    /// CHECK: Validated by the token-metadata program during the transfer
+    #[account(mut)]
+    pub asset_metadata: Option<UncheckedAccount<'info>>,
+    
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
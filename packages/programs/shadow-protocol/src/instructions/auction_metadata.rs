@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+
+/// Fields shared by metadata creation and updates
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AuctionMetadataArgs {
+    pub title: String,
+    pub description: String,
+    pub category: String,
+    pub uri: String,
+    pub content_hash: [u8; 32],
+}
+
+fn write_metadata(
+    metadata: &mut AuctionMetadata,
+    auction: &AuctionAccount,
+    args: AuctionMetadataArgs,
+) -> Result<()> {
+    // Listings are frozen once bidders may have relied on them
+    require!(
+        auction.bid_count == 0
+            && (auction.status == AuctionStatus::Created || auction.status == AuctionStatus::Active),
+        ShadowProtocolError::MetadataLocked
+    );
+    
+    require!(
+        args.title.len() <= MAX_TITLE_LEN
+            && args.description.len() <= MAX_DESCRIPTION_LEN
+            && args.category.len() <= MAX_CATEGORY_LEN
+            && args.uri.len() <= MAX_URI_LEN,
+        ShadowProtocolError::MetadataTooLong
+    );
+    
+    metadata.title = args.title;
+    metadata.description = args.description;
+    metadata.category = args.category;
+    metadata.uri = args.uri;
+    metadata.content_hash = args.content_hash;
+    metadata.updated_at = Clock::get()?.unix_timestamp;
+    
+    emit!(AuctionMetadataUpdated {
+        auction_id: auction.auction_id,
+        uri: metadata.uri.clone(),
+        content_hash: metadata.content_hash,
+    });
+    
+    Ok(())
+}
+
+/// Publish title, description, category and media URI for an auction.
+/// Send it in the same transaction as the auction's creation.
+pub fn create_auction_metadata(
+    ctx: Context<CreateAuctionMetadata>,
+    auction_id: u64,
+    args: AuctionMetadataArgs,
+) -> Result<()> {
+    let metadata = &mut ctx.accounts.metadata;
+    metadata.auction_id = auction_id;
+    metadata.creator = ctx.accounts.creator.key();
+    metadata.bump = ctx.bumps.metadata;
+    
+    write_metadata(metadata, &ctx.accounts.auction, args)
+}
+
+/// Edit an auction's metadata (creator only, before the first bid)
+pub fn update_auction_metadata(
+    ctx: Context<UpdateAuctionMetadata>,
+    _auction_id: u64,
+    args: AuctionMetadataArgs,
+) -> Result<()> {
+    write_metadata(&mut ctx.accounts.metadata, &ctx.accounts.auction, args)
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CreateAuctionMetadata<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AuctionMetadata::INIT_SPACE,
+        seeds = [AUCTION_METADATA_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub metadata: Account<'info, AuctionMetadata>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct UpdateAuctionMetadata<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_METADATA_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = metadata.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized
+    )]
+    pub metadata: Account<'info, AuctionMetadata>,
+}
\ No newline at end of file
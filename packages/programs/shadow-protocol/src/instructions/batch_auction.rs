@@ -0,0 +1,686 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+use super::auction_management::current_auction_time;
+use super::admin::{require_role, require_not_paused};
+use super::settlement::calculate_protocol_fee;
+
+/// Open a batch auction epoch for a base/quote token pair. Orders collected
+/// until `end_time` are cleared together at a single uniform price.
+pub fn create_batch_auction(
+    ctx: Context<CreateBatchAuction>,
+    base_mint: Pubkey,
+    epoch_duration: u64,
+) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    require_not_paused(protocol, PAUSE_CREATE, AuctionType::Batch)?;
+    
+    require!(
+        ctx.accounts.base_mint.key() != ctx.accounts.quote_mint.key(),
+        ShadowProtocolError::InvalidPaymentMint
+    );
+    
+    // Get the auction ID from protocol state
+    let auction_id = protocol.next_auction_id;
+    
+    // Increment next auction ID with overflow check
+    protocol.next_auction_id = protocol.next_auction_id
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    let clock = Clock::get()?;
+    let start_time = clock.unix_timestamp;
+    let end_time = start_time + epoch_duration as i64;
+    
+    require!(
+        epoch_duration as i64 <= MAX_AUCTION_DURATION,
+        ShadowProtocolError::AuctionDurationTooLong
+    );
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.auction_id = auction_id;
+    auction.creator = ctx.accounts.creator.key();
+    auction.asset_mint = base_mint;
+    auction.asset_vault = ctx.accounts.asset_vault.key();
+    auction.asset_amount = 0;
+    auction.auction_type = AuctionType::Batch;
+    auction.status = AuctionStatus::Active;
+    auction.start_time = start_time;
+    auction.end_time = end_time;
+    auction.minimum_bid = 0;
+    auction.minimum_price_floor = 0;
+    auction.reserve_price_encrypted = [0u8; 32];
+    auction.reserve_price_nonce = 0;
+    auction.current_price = 0;
+    auction.price_decrease_rate = 0;
+    auction.bid_count = 0;
+    auction.winner = None;
+    auction.winning_amount = 0;
+    auction.settled_at = None;
+    auction.mpc_verification_hash = None;
+    auction.settlement_authorized = false;
+    auction.total_units = 0;
+    auction.units_allocated = 0;
+    auction.clearing_price = 0;
+    auction.bids_settled = 0;
+    auction.payment_mint = ctx.accounts.quote_mint.key();
+    auction.pricing_rule = PricingRule::FirstPrice;
+    auction.budget = 0;
+    auction.bundle = None;
+    auction.batch = None;
+    auction.protocol_fee_bps = protocol.protocol_fee;
+    auction.referral_fee_bps = protocol.referral_fee_bps;
+    auction.frozen = false;
+    auction.bids_closed = 0;
+    auction.gate = None;
+    auction.bid_bond = 0;
+    auction.nft = None;
+    auction.usd_pricing = None;
+    auction.timing_mode = TimingMode::Timestamp;
+    auction.keeper_bounty = 0;
+    auction.keeper = None;
+    auction.allocations_recorded = 0;
+    auction.nft_royalty_bps = None;
+    auction.version = AUCTION_ACCOUNT_VERSION;
+    auction.bump = ctx.bumps.auction;
+    
+    emit!(AuctionCreated {
+        auction_id,
+        creator: ctx.accounts.creator.key(),
+        asset_mint: base_mint,
+        auction_type: AuctionType::Batch,
+        start_time,
+        end_time,
+        minimum_bid: 0,
+        timing_mode: TimingMode::Timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Submit an encrypted limit order to an open epoch. Side, price and size
+/// stay hidden; the trader deposits the base and/or quote tokens the order
+/// may need, and any unused deposit is refunded at settlement.
+#[allow(clippy::too_many_arguments)]
+pub fn submit_batch_order(
+    ctx: Context<SubmitBatchOrder>,
+    auction_id: u64,
+    side_encrypted: [u8; 32],
+    price_encrypted: [u8; 32],
+    size_encrypted: [u8; 32],
+    public_key: [u8; 32],
+    nonce: u128,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    require_not_paused(protocol, PAUSE_BID, AuctionType::Batch)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.status == AuctionStatus::Active,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(
+        current_auction_time(auction.timing_mode, &clock) < auction.end_time,
+        ShadowProtocolError::AuctionEnded
+    );
+    
+    require!(
+        auction.bid_count < MAX_ORDERS_PER_BATCH,
+        ShadowProtocolError::MaxBidsExceeded
+    );
+    
+    require!(
+        base_amount > 0 || quote_amount > 0,
+        ShadowProtocolError::EmptyOrderDeposit
+    );
+    
+    // Validate deposits are reasonable (prevent overflow attacks)
+    require!(
+        base_amount <= u64::MAX / 2 && quote_amount <= u64::MAX / 2,
+        ShadowProtocolError::InvalidAssetAmount
+    );
+    
+    let order = &mut ctx.accounts.order;
+    order.auction_id = auction_id;
+    order.trader = ctx.accounts.trader.key();
+    order.side_encrypted = side_encrypted;
+    order.price_encrypted = price_encrypted;
+    order.size_encrypted = size_encrypted;
+    order.encryption_public_key = public_key;
+    order.nonce = nonce;
+    order.timestamp = clock.unix_timestamp;
+    order.base_deposited = base_amount;
+    order.quote_deposited = quote_amount;
+    order.fill = None;
+    order.settled = false;
+    order.bump = ctx.bumps.order;
+    
+    if base_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.trader_base_account.to_account_info(),
+                    to: ctx.accounts.asset_vault.to_account_info(),
+                    authority: ctx.accounts.trader.to_account_info(),
+                },
+            ),
+            base_amount,
+        )?;
+    }
+    
+    if quote_amount > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.trader_quote_account.to_account_info(),
+                    to: ctx.accounts.quote_vault.to_account_info(),
+                    authority: ctx.accounts.trader.to_account_info(),
+                },
+            ),
+            quote_amount,
+        )?;
+    }
+    
+    auction.bid_count += 1;
+    
+    emit!(BatchOrderSubmitted {
+        auction_id,
+        trader: ctx.accounts.trader.key(),
+        timestamp: clock.unix_timestamp,
+        order_count: auction.bid_count,
+    });
+    
+    Ok(())
+}
+
+/// Record the uniform clearing price and per-order fills computed by the
+/// batch auction MPC circuit. Order accounts are passed as remaining accounts
+/// in the same order as `fills`; orders left out are treated as unfilled.
+pub fn record_batch_fills<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RecordBatchFills<'info>>,
+    auction_id: u64,
+    clearing_price: u64,
+    fills: Vec<BatchFill>,
+    mpc_verification_hash: [u8; 32],
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    
+    // Only the protocol authority or a settlement attester can attest the clearing result
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::SettlementAttester,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    require!(
+        auction.auction_type == AuctionType::Batch,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(
+        !auction.settlement_authorized,
+        ShadowProtocolError::AuctionAlreadySettled
+    );
+    
+    require!(
+        fills.len() == ctx.remaining_accounts.len(),
+        ShadowProtocolError::InvalidWinnerDetermination
+    );
+    
+    let mut bought: u64 = 0;
+    let mut sold: u64 = 0;
+    let mut filled_orders: u64 = 0;
+    
+    for (order_info, fill) in ctx.remaining_accounts.iter().zip(fills.iter()) {
+        let mut order: Account<'info, BatchOrder> = Account::try_from(order_info)?;
+        
+        require!(
+            order.auction_id == auction_id,
+            ShadowProtocolError::InvalidBidAccount
+        );
+        
+        require!(order.fill.is_none(), ShadowProtocolError::OrderAlreadyFilled);
+        
+        if fill.quantity == 0 {
+            continue;
+        }
+        
+        match fill.side {
+            OrderSide::Buy => {
+                // Buyers must have deposited enough quote to pay the clearing price
+                let cost = fill.quantity
+                    .checked_mul(clearing_price)
+                    .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+                require!(
+                    cost <= order.quote_deposited,
+                    ShadowProtocolError::FillExceedsDeposit
+                );
+                bought = bought
+                    .checked_add(fill.quantity)
+                    .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+            }
+            OrderSide::Sell => {
+                require!(
+                    fill.quantity <= order.base_deposited,
+                    ShadowProtocolError::FillExceedsDeposit
+                );
+                sold = sold
+                    .checked_add(fill.quantity)
+                    .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+            }
+        }
+        
+        order.fill = Some(*fill);
+        order.exit(&crate::ID)?;
+        filled_orders += 1;
+    }
+    
+    // Every base unit bought must come from a seller in the same epoch
+    require!(bought == sold, ShadowProtocolError::UnbalancedBatchFills);
+    
+    auction.clearing_price = clearing_price;
+    auction.units_allocated = bought;
+    auction.mpc_verification_hash = Some(mpc_verification_hash);
+    auction.settlement_authorized = true;
+    
+    emit!(BatchAuctionCleared {
+        auction_id,
+        clearing_price,
+        matched_volume: bought,
+        filled_orders,
+    });
+    
+    msg!("Batch auction {} cleared at {}, {} base units matched",
+         auction_id, clearing_price, bought);
+    
+    Ok(())
+}
+
+/// Pay out one order of a cleared (or cancelled) epoch: the filled side of
+/// the trade at the clearing price plus any unused deposit.
+pub fn settle_batch_order(ctx: Context<SettleBatchOrder>, auction_id: u64) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let order = &mut ctx.accounts.order;
+    
+    require!(
+        auction.auction_type == AuctionType::Batch,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    // Cancelled epochs refund every order in full
+    require!(
+        (auction.settlement_authorized && auction.status == AuctionStatus::Ended)
+            || auction.status == AuctionStatus::Cancelled,
+        ShadowProtocolError::SettlementNotAuthorized
+    );
+    
+    // Refunds from cancelled epochs are never blocked
+    if auction.status != AuctionStatus::Cancelled {
+        require_not_paused(&ctx.accounts.protocol_state, PAUSE_SETTLE, AuctionType::Batch)?;
+        require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    }
+    
+    require!(!order.settled, ShadowProtocolError::BidAlreadySettled);
+    
+    let mut fee_amount: u64 = 0;
+    let (base_out, quote_out) = match order.fill {
+        Some(BatchFill { side: OrderSide::Buy, quantity }) => {
+            let cost = quantity
+                .checked_mul(auction.clearing_price)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+            let quote_refund = order.quote_deposited
+                .checked_sub(cost)
+                .ok_or(ShadowProtocolError::FillExceedsDeposit)?;
+            let base_out = order.base_deposited
+                .checked_add(quantity)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+            (base_out, quote_refund)
+        }
+        Some(BatchFill { side: OrderSide::Sell, quantity }) => {
+            let proceeds = quantity
+                .checked_mul(auction.clearing_price)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+            fee_amount = calculate_protocol_fee(proceeds, auction.protocol_fee_bps)?;
+            let base_refund = order.base_deposited
+                .checked_sub(quantity)
+                .ok_or(ShadowProtocolError::FillExceedsDeposit)?;
+            let quote_out = order.quote_deposited
+                .checked_add(proceeds - fee_amount)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+            (base_refund, quote_out)
+        }
+        None => (order.base_deposited, order.quote_deposited),
+    };
+    
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let signer_seeds = &[&auction_seeds[..]];
+    
+    if base_out > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.asset_vault.to_account_info(),
+                    to: ctx.accounts.trader_base_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            base_out,
+        )?;
+    }
+    
+    if quote_out > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.quote_vault.to_account_info(),
+                    to: ctx.accounts.trader_quote_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            quote_out,
+        )?;
+    }
+    
+    // Transfer fee to protocol
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.quote_vault.to_account_info(),
+                    to: ctx.accounts.protocol_fee_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            fee_amount,
+        )?;
+        
+        emit!(ProtocolFeeCollected {
+            auction_id,
+            mint: ctx.accounts.protocol_fee_account.mint,
+            amount: fee_amount,
+        });
+    }
+    
+    order.settled = true;
+    auction.bids_settled = auction.bids_settled
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    emit!(BatchOrderSettled {
+        auction_id,
+        trader: order.trader,
+        fill: order.fill,
+        base_out,
+        quote_out,
+    });
+    
+    // Close out the epoch once every order has been paid
+    if auction.bids_settled == auction.bid_count && auction.status == AuctionStatus::Ended {
+        let settled_at = Clock::get()?.unix_timestamp;
+        auction.status = AuctionStatus::Settled;
+        auction.settled_at = Some(settled_at);
+        
+        emit!(AuctionSettled {
+            auction_id,
+            winner: None,
+            winning_amount: auction.clearing_price,
+            splits: None,
+            settled_at,
+        });
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateBatchAuction<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AuctionAccount::INIT_SPACE,
+        seeds = [AUCTION_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    pub base_mint: Account<'info, Mint>,
+    
+    pub quote_mint: Account<'info, Mint>,
+    
+    /// Epoch vault pooling base token deposits
+    #[account(
+        init,
+        payer = creator,
+        token::mint = base_mint,
+        token::authority = auction,
+        seeds = [ASSET_VAULT_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+    
+    /// Epoch vault pooling quote token deposits
+    #[account(
+        init,
+        payer = creator,
+        token::mint = quote_mint,
+        token::authority = auction,
+        seeds = [QUOTE_VAULT_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct SubmitBatchOrder<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        constraint = auction.auction_type == AuctionType::Batch @ ShadowProtocolError::InvalidAuctionType
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        init,
+        payer = trader,
+        space = 8 + BatchOrder::INIT_SPACE,
+        seeds = [ORDER_SEED, auction_id.to_le_bytes().as_ref(), trader.key().as_ref()],
+        bump
+    )]
+    pub order: Account<'info, BatchOrder>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Trader's base token account
+    #[account(
+        mut,
+        associated_token::mint = auction.asset_mint,
+        associated_token::authority = trader
+    )]
+    pub trader_base_account: Account<'info, TokenAccount>,
+    
+    /// Trader's quote token account
+    #[account(
+        mut,
+        associated_token::mint = auction.payment_mint,
+        associated_token::authority = trader
+    )]
+    pub trader_quote_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [ASSET_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [QUOTE_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct RecordBatchFills<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct SettleBatchOrder<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [ORDER_SEED, auction_id.to_le_bytes().as_ref(), order.trader.as_ref()],
+        bump = order.bump
+    )]
+    pub order: Account<'info, BatchOrder>,
+    
+    #[account(
+        mut,
+        seeds = [ASSET_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [QUOTE_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+    
+    /// Trader's base token account
+    #[account(
+        mut,
+        associated_token::mint = auction.asset_mint,
+        associated_token::authority = order.trader
+    )]
+    pub trader_base_account: Account<'info, TokenAccount>,
+    
+    /// Trader's quote token account
+    #[account(
+        mut,
+        associated_token::mint = auction.payment_mint,
+        associated_token::authority = order.trader
+    )]
+    pub trader_quote_account: Account<'info, TokenAccount>,
+    
+    /// Payment token mint
+    #[account(address = auction.payment_mint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    /// Treasury PDA that owns the per-mint fee vaults
+    /// CHECK: PDA used only as the fee vault authority
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Treasury fee vault for the payment mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = settler,
+        token::mint = payment_mint,
+        token::authority = treasury,
+        seeds = [FEE_VAULT_SEED, payment_mint.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer, CloseAccount, close_account};
 // TODO: Re-enable after fixing Arcium imports
 // use arcium_anchor::{queue_computation};
 // use arcium_client::idl::arcium::{
@@ -8,18 +9,22 @@ use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
 // };
 use crate::state::*;
 use crate::error::ShadowProtocolError;
+use super::auction_management::current_auction_time;
+use super::oracle::usd_to_payment_amount;
+use super::admin::require_not_paused;
+use super::gating::require_bidder_allowed;
 
-/// Calculate current Dutch auction price based on time progression
-fn calculate_dutch_auction_price(auction: &AuctionAccount) -> Result<u64> {
+/// Calculate current Dutch auction price based on time or slot progression
+pub(crate) fn calculate_dutch_auction_price(auction: &AuctionAccount) -> Result<u64> {
     let clock = Clock::get()?;
-    let current_time = clock.unix_timestamp;
+    let current_time = current_auction_time(auction.timing_mode, &clock);
     let elapsed_time = current_time.saturating_sub(auction.start_time);
     
     // Ensure elapsed time is non-negative
-    let elapsed_seconds = elapsed_time.max(0) as u64;
+    let elapsed_units = elapsed_time.max(0) as u64;
     
-    // Calculate price decrease based on time (price_decrease_rate is per second)
-    let price_decrease = elapsed_seconds
+    // price_decrease_rate is per second, or per slot for slot-timed auctions
+    let price_decrease = elapsed_units
         .checked_mul(auction.price_decrease_rate)
         .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
     
@@ -31,6 +36,17 @@ fn calculate_dutch_auction_price(auction: &AuctionAccount) -> Result<u64> {
     Ok(current_price)
 }
 
+/// Mint every bid on `auction` escrows its collateral and pays its bond in.
+/// Suppliers in reverse auctions escrow the asset they offer so settlement
+/// is atomic; everyone else bids in the auction's payment mint.
+pub(crate) fn bid_collateral_mint(auction: &AuctionAccount) -> Pubkey {
+    match auction.auction_type {
+        AuctionType::Reverse => auction.asset_mint,
+        _ => auction.payment_mint,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn submit_encrypted_bid(
     ctx: Context<SubmitBid>,
     auction_id: u64,
@@ -38,13 +54,39 @@ pub fn submit_encrypted_bid(
     public_key: [u8; 32],
     nonce: u128,
     collateral_amount: u64,
-    computation_offset: u64,
+    referrer: Option<Pubkey>,
+    allowlist_proof: Vec<[u8; 32]>,
+    _computation_offset: u64,
 ) -> Result<()> {
+    // Cap open bids per wallet, hold the spam deposit and pay the bid bond
+    ctx.accounts.apply_bid_limits(ctx.bumps.bidder_stats)?;
+    
     let auction = &mut ctx.accounts.auction;
     let protocol = &ctx.accounts.protocol_state;
     let clock = Clock::get()?;
     
-    require!(!protocol.paused, ShadowProtocolError::ProtocolPaused);
+    // Bidders cannot refer themselves to claw back part of the price
+    require!(
+        referrer != Some(ctx.accounts.bidder.key()),
+        ShadowProtocolError::InvalidReferrer
+    );
+    
+    require_not_paused(protocol, PAUSE_BID, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.auction_type == AuctionType::SealedBid,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require_bidder_allowed(
+        auction,
+        &ctx.accounts.bidder.key(),
+        &allowlist_proof,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_attester.as_ref(),
+    )?;
     
     require!(
         auction.status == AuctionStatus::Active,
@@ -56,6 +98,15 @@ pub fn submit_encrypted_bid(
         ShadowProtocolError::InsufficientCollateral
     );
     
+    // USD minimum, converted at the oracle price when the bid is placed
+    if let Some(pricing) = &auction.usd_pricing {
+        let minimum_bid = usd_to_payment_amount(pricing, ctx.accounts.price_feed.as_ref(), pricing.minimum_bid_usd)?;
+        require!(
+            collateral_amount >= minimum_bid,
+            ShadowProtocolError::InsufficientCollateral
+        );
+    }
+    
     // Validate collateral amount is reasonable (prevent overflow attacks)
     require!(
         collateral_amount <= u64::MAX / 2,
@@ -69,7 +120,7 @@ pub fn submit_encrypted_bid(
     );
     
     require!(
-        clock.unix_timestamp < auction.end_time,
+        current_auction_time(auction.timing_mode, &clock) < auction.end_time,
         ShadowProtocolError::AuctionEnded
     );
     
@@ -88,6 +139,12 @@ pub fn submit_encrypted_bid(
     bid.collateral_amount = collateral_amount;
     bid.collateral_account = ctx.accounts.bidder_collateral_account.key();
     bid.is_winner = false;
+    bid.quantity_encrypted = [0u8; 32];
+    bid.units_allocated = 0;
+    bid.settled = false;
+    bid.allocation_recorded = false;
+    bid.referrer = referrer;
+    bid.version = BID_ACCOUNT_VERSION;
     bid.bump = ctx.bumps.bid;
     
     // Transfer collateral to bid escrow
@@ -137,17 +194,132 @@ pub fn submit_encrypted_bid(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn submit_multi_unit_bid(
+    ctx: Context<SubmitBid>,
+    auction_id: u64,
+    price_encrypted: [u8; 32],
+    quantity_encrypted: [u8; 32],
+    public_key: [u8; 32],
+    nonce: u128,
+    collateral_amount: u64,
+    _computation_offset: u64,
+) -> Result<()> {
+    // Cap open bids per wallet, hold the spam deposit and pay the bid bond
+    ctx.accounts.apply_bid_limits(ctx.bumps.bidder_stats)?;
+    
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    require_not_paused(protocol, PAUSE_BID, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.auction_type == AuctionType::MultiUnit,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Active,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    // Collateral must cover at least one unit at the minimum price; the
+    // clearing computation caps each allocation at what the collateral covers
+    require!(
+        collateral_amount >= auction.minimum_bid,
+        ShadowProtocolError::InsufficientCollateral
+    );
+    
+    // Validate collateral amount is reasonable (prevent overflow attacks)
+    require!(
+        collateral_amount <= u64::MAX / 2,
+        ShadowProtocolError::InvalidAssetAmount
+    );
+    
+    // Validate bidder has sufficient collateral
+    require!(
+        ctx.accounts.bidder_collateral_account.amount >= collateral_amount,
+        ShadowProtocolError::InsufficientFunds
+    );
+    
+    require!(
+        current_auction_time(auction.timing_mode, &clock) < auction.end_time,
+        ShadowProtocolError::AuctionEnded
+    );
+    
+    require!(
+        auction.bid_count < MAX_BIDS_PER_AUCTION as u64,
+        ShadowProtocolError::MaxBidsExceeded
+    );
+    
+    let bid = &mut ctx.accounts.bid;
+    bid.auction_id = auction_id;
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.amount_encrypted = price_encrypted;
+    bid.encryption_public_key = public_key;
+    bid.nonce = nonce;
+    bid.timestamp = clock.unix_timestamp;
+    bid.collateral_amount = collateral_amount;
+    bid.collateral_account = ctx.accounts.bidder_collateral_account.key();
+    bid.is_winner = false;
+    bid.quantity_encrypted = quantity_encrypted;
+    bid.units_allocated = 0;
+    bid.settled = false;
+    bid.allocation_recorded = false;
+    bid.referrer = None;
+    bid.version = BID_ACCOUNT_VERSION;
+    bid.bump = ctx.bumps.bid;
+    
+    // Transfer collateral to bid escrow
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder_collateral_account.to_account_info(),
+                to: ctx.accounts.bid_escrow.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            },
+        ),
+        collateral_amount,
+    )?;
+    
+    auction.bid_count += 1;
+    
+    emit!(BidSubmitted {
+        auction_id,
+        bidder: ctx.accounts.bidder.key(),
+        timestamp: clock.unix_timestamp,
+        bid_count: auction.bid_count,
+    });
+    
+    Ok(())
+}
+
 pub fn submit_dutch_bid(
     ctx: Context<SubmitDutchBid>,
     auction_id: u64,
     bid_amount: u64,
     collateral_amount: u64,
+    allowlist_proof: Vec<[u8; 32]>,
 ) -> Result<()> {
     let auction = &mut ctx.accounts.auction;
     let protocol = &ctx.accounts.protocol_state;
     let clock = Clock::get()?;
     
-    require!(!protocol.paused, ShadowProtocolError::ProtocolPaused);
+    require_not_paused(protocol, PAUSE_BID, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require_bidder_allowed(
+        auction,
+        &ctx.accounts.bidder.key(),
+        &allowlist_proof,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_attester.as_ref(),
+    )?;
     
     require!(
         auction.auction_type == AuctionType::Dutch,
@@ -177,12 +349,22 @@ pub fn submit_dutch_bid(
     );
     
     require!(
-        clock.unix_timestamp < auction.end_time,
+        current_auction_time(auction.timing_mode, &clock) < auction.end_time,
         ShadowProtocolError::AuctionEnded
     );
     
-    // Calculate current price based on slot progression (more reliable than timestamps)
-    let current_price = calculate_dutch_auction_price(auction)?;
+    // Calculate current price from elapsed seconds or slots (per the timing mode)
+    let mut current_price = calculate_dutch_auction_price(auction)?;
+    
+    // USD floor, converted at the oracle price when the bid is placed
+    if let Some(pricing) = &auction.usd_pricing {
+        let floor = usd_to_payment_amount(
+            pricing,
+            ctx.accounts.price_feed.as_ref(),
+            pricing.minimum_price_floor_usd,
+        )?;
+        current_price = current_price.max(floor);
+    }
     
     require!(
         bid_amount >= current_price,
@@ -194,13 +376,14 @@ pub fn submit_dutch_bid(
         ShadowProtocolError::PriceBelowMinimumFloor
     );
     
-    // Transfer collateral from bidder
+    // Escrow collateral until the auction is settled or cancelled; the
+    // winner pays the price at settlement and reclaims it with claim_refund
     transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.bidder_collateral_account.to_account_info(),
-                to: ctx.accounts.protocol_payment_account.to_account_info(),
+                to: ctx.accounts.bid_escrow.to_account_info(),
                 authority: ctx.accounts.bidder.to_account_info(),
             },
         ),
@@ -245,6 +428,208 @@ pub fn submit_dutch_bid(
     Ok(())
 }
 
+pub fn submit_encrypted_ask(
+    ctx: Context<SubmitBid>,
+    auction_id: u64,
+    ask_amount_encrypted: [u8; 32],
+    public_key: [u8; 32],
+    nonce: u128,
+    _computation_offset: u64,
+) -> Result<()> {
+    // Cap open bids per wallet, hold the spam deposit and pay the bid bond
+    ctx.accounts.apply_bid_limits(ctx.bumps.bidder_stats)?;
+    
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    require_not_paused(protocol, PAUSE_BID, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.auction_type == AuctionType::Reverse,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Active,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    let collateral_amount = auction.asset_amount;
+    
+    // Validate supplier holds the full asset amount
+    require!(
+        ctx.accounts.bidder_collateral_account.amount >= collateral_amount,
+        ShadowProtocolError::InsufficientFunds
+    );
+    
+    require!(
+        current_auction_time(auction.timing_mode, &clock) < auction.end_time,
+        ShadowProtocolError::AuctionEnded
+    );
+    
+    require!(
+        auction.bid_count < MAX_BIDS_PER_AUCTION as u64,
+        ShadowProtocolError::MaxBidsExceeded
+    );
+    
+    let bid = &mut ctx.accounts.bid;
+    bid.auction_id = auction_id;
+    bid.bidder = ctx.accounts.bidder.key();
+    bid.amount_encrypted = ask_amount_encrypted;
+    bid.encryption_public_key = public_key;
+    bid.nonce = nonce;
+    bid.timestamp = clock.unix_timestamp;
+    bid.collateral_amount = collateral_amount;
+    bid.collateral_account = ctx.accounts.bidder_collateral_account.key();
+    bid.is_winner = false;
+    bid.quantity_encrypted = [0u8; 32];
+    bid.units_allocated = 0;
+    bid.settled = false;
+    bid.allocation_recorded = false;
+    bid.referrer = None;
+    bid.version = BID_ACCOUNT_VERSION;
+    bid.bump = ctx.bumps.bid;
+    
+    // Transfer offered asset to bid escrow
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.bidder_collateral_account.to_account_info(),
+                to: ctx.accounts.bid_escrow.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            },
+        ),
+        collateral_amount,
+    )?;
+    
+    auction.bid_count += 1;
+    
+    emit!(BidSubmitted {
+        auction_id,
+        bidder: ctx.accounts.bidder.key(),
+        timestamp: clock.unix_timestamp,
+        bid_count: auction.bid_count,
+    });
+    
+    Ok(())
+}
+
+/// Return escrowed collateral for a bid that was not consumed by settlement.
+/// Dutch bids have no bid account; their escrow is emptied and closed, so
+/// its rent comes back with the collateral.
+pub fn claim_refund(ctx: Context<ClaimRefund>, auction_id: u64) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    
+    require!(
+        auction.status == AuctionStatus::Settled || auction.status == AuctionStatus::Cancelled,
+        ShadowProtocolError::RefundNotAvailable
+    );
+    
+    let amount = ctx.accounts.bid_escrow.amount;
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let bidder_key = ctx.accounts.bidder.key();
+    
+    match ctx.accounts.bid.as_mut() {
+        Some(bid) => {
+            require!(
+                ctx.accounts.bidder_collateral_account.key() == bid.collateral_account,
+                ShadowProtocolError::InvalidEscrowAccount
+            );
+            
+            // Settled or already-refunded bids have nothing left in escrow
+            require!(!bid.settled, ShadowProtocolError::RefundNotAvailable);
+            
+            let bid_seeds = &[
+                BID_SEED,
+                auction_id_bytes.as_ref(),
+                bidder_key.as_ref(),
+                &[bid.bump],
+            ];
+            let signer_seeds = &[&bid_seeds[..]];
+            
+            if amount > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.bid_escrow.to_account_info(),
+                            to: ctx.accounts.bidder_collateral_account.to_account_info(),
+                            authority: bid.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    amount,
+                )?;
+            }
+            
+            bid.settled = true;
+            
+            // Release the anti-spam deposit held on the bid account
+            let deposit = bid.deposit_lamports;
+            if deposit > 0 {
+                bid.deposit_lamports = 0;
+                bid.sub_lamports(deposit)?;
+                ctx.accounts.bidder.add_lamports(deposit)?;
+            }
+        }
+        // Only Dutch bids escrow collateral without a bid account
+        None => {
+            require!(
+                auction.auction_type == AuctionType::Dutch,
+                ShadowProtocolError::InvalidEscrowAccount
+            );
+            require!(
+                ctx.accounts.bidder_collateral_account.owner == bidder_key,
+                ShadowProtocolError::InvalidEscrowAccount
+            );
+            
+            let auction_seeds = &[
+                AUCTION_SEED,
+                auction_id_bytes.as_ref(),
+                &[auction.bump],
+            ];
+            let signer_seeds = &[&auction_seeds[..]];
+            
+            if amount > 0 {
+                transfer(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.bid_escrow.to_account_info(),
+                            to: ctx.accounts.bidder_collateral_account.to_account_info(),
+                            authority: auction.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    amount,
+                )?;
+            }
+            
+            close_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                CloseAccount {
+                    account: ctx.accounts.bid_escrow.to_account_info(),
+                    destination: ctx.accounts.bidder.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+    }
+    
+    emit!(BidRefunded {
+        auction_id,
+        bidder: bidder_key,
+        amount,
+    });
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
 #[instruction(auction_id: u64)]
 pub struct SubmitBid<'info> {
@@ -287,14 +672,40 @@ pub struct SubmitBid<'info> {
         payer = bidder,
         token::mint = collateral_mint,
         token::authority = bid,
-        seeds = [b"bid_escrow", auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        seeds = [BID_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
         bump
     )]
     pub bid_escrow: Account<'info, TokenAccount>,
     
-    /// Collateral token mint
+    /// Collateral token mint, fixed by the auction (see `bid_collateral_mint`)
+    #[account(address = bid_collateral_mint(&auction) @ ShadowProtocolError::InvalidPaymentMint)]
     pub collateral_mint: Account<'info, Mint>,
     
+    /// Bidder's open bid count across auctions
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + BidderStats::INIT_SPACE,
+        seeds = [BIDDER_STATS_SEED, bidder.key().as_ref()],
+        bump
+    )]
+    pub bidder_stats: Account<'info, BidderStats>,
+    
+    /// Seller's account receiving the bid bond in the collateral mint
+    /// (required when the auction has one)
+    #[account(mut)]
+    pub creator_bond_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Oracle price account (required when the auction has USD pricing)
+    /// CHECK: Matched against the auction's price feed and parsed in the handler
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// Bidder's token account for token-holding gates
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Attester co-signing bids on attester-gated auctions
+    pub gate_attester: Option<Signer<'info>>,
+    
     // TODO: Re-enable after fixing Arcium imports
     // pub computation_definition: Account<'info, ComputationDefinitionAccount>,
     // pub mxe: Account<'info, PersistentMXEAccount>,
@@ -303,6 +714,91 @@ pub struct SubmitBid<'info> {
     pub system_program: Program<'info, System>,
 }
 
+/// Counts a newly opened bid against the per-wallet cap and holds the
+/// refundable anti-spam deposit on the bid account
+pub(crate) fn open_bid_slot<'info>(
+    protocol: &ProtocolState,
+    stats: &mut BidderStats,
+    stats_bump: u8,
+    bidder: &Signer<'info>,
+    bid: &mut Account<'info, BidAccount>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if protocol.max_active_bids_per_wallet > 0 {
+        require!(
+            stats.active_bids < protocol.max_active_bids_per_wallet,
+            ShadowProtocolError::TooManyActiveBids
+        );
+    }
+    
+    stats.bidder = bidder.key();
+    stats.active_bids = stats.active_bids
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    stats.bump = stats_bump;
+    
+    // Refundable deposit, held as extra lamports on the bid account
+    let deposit = protocol.bid_deposit_lamports;
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: bidder.to_account_info(),
+                    to: bid.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+    bid.deposit_lamports = deposit;
+    
+    Ok(())
+}
+
+impl<'info> SubmitBid<'info> {
+    /// Anti-spam rules shared by every instruction that opens a bid account
+    fn apply_bid_limits(&mut self, bidder_stats_bump: u8) -> Result<()> {
+        open_bid_slot(
+            &self.protocol_state,
+            &mut self.bidder_stats,
+            bidder_stats_bump,
+            &self.bidder,
+            &mut self.bid,
+            &self.system_program,
+        )?;
+        
+        // Non-refundable bond paid straight to the seller, denominated in
+        // the auction's collateral mint rather than whatever the bidder sends
+        let bond = self.auction.bid_bond;
+        if bond > 0 {
+            let creator_bond_account = self.creator_bond_account
+                .as_ref()
+                .ok_or(ShadowProtocolError::InvalidBidBondAccount)?;
+            require!(
+                creator_bond_account.owner == self.auction.creator
+                    && creator_bond_account.mint == bid_collateral_mint(&self.auction),
+                ShadowProtocolError::InvalidBidBondAccount
+            );
+            
+            transfer(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    Transfer {
+                        from: self.bidder_collateral_account.to_account_info(),
+                        to: creator_bond_account.to_account_info(),
+                        authority: self.bidder.to_account_info(),
+                    },
+                ),
+                bond,
+            )?;
+        }
+        
+        Ok(())
+    }
+}
+
+
 #[derive(Accounts)]
 #[instruction(auction_id: u64)]
 pub struct SubmitDutchBid<'info> {
@@ -331,16 +827,32 @@ pub struct SubmitDutchBid<'info> {
     )]
     pub bidder_collateral_account: Account<'info, TokenAccount>,
     
-    /// Protocol payment account
+    /// Escrow holding the collateral. Dutch bids have no bid account, so
+    /// the auction PDA is the escrow authority.
     #[account(
-        mut,
-        address = protocol_state.fee_recipient
+        init,
+        payer = bidder,
+        token::mint = collateral_mint,
+        token::authority = auction,
+        seeds = [BID_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump
     )]
-    pub protocol_payment_account: Account<'info, TokenAccount>,
+    pub bid_escrow: Account<'info, TokenAccount>,
     
     /// Collateral token mint
+    #[account(address = auction.payment_mint @ ShadowProtocolError::InvalidPaymentMint)]
     pub collateral_mint: Account<'info, Mint>,
     
+    /// Oracle price account (required when the auction has USD pricing)
+    /// CHECK: Matched against the auction's price feed and parsed in the handler
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// Bidder's token account for token-holding gates
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Attester co-signing bids on attester-gated auctions
+    pub gate_attester: Option<Signer<'info>>,
+    
     // TODO: Re-enable after fixing Arcium imports
     // pub computation_definition: Account<'info, ComputationDefinitionAccount>,
     // pub mxe: Account<'info, PersistentMXEAccount>,
@@ -349,6 +861,46 @@ pub struct SubmitDutchBid<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    /// Sealed bid the collateral backs (omitted for Dutch bids)
+    #[account(
+        mut,
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        has_one = bidder
+    )]
+    pub bid: Option<Account<'info, BidAccount>>,
+    
+    /// Bid escrow holding the collateral
+    #[account(
+        mut,
+        seeds = [BID_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_escrow: Account<'info, TokenAccount>,
+    
+    /// Account the collateral is returned to: the one it was deposited from
+    /// for sealed bids, any of the bidder's accounts for the mint otherwise
+    #[account(
+        mut,
+        token::mint = bid_escrow.mint
+    )]
+    pub bidder_collateral_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
 // TODO: Re-enable after fixing Arcium imports
 // impl<'info> SubmitBid<'info> {
 //     pub fn queue_computation_ctx(&self) -> CpiContext<'_, '_, '_, 'info, arcium_anchor::QueueComputation<'info>> {
@@ -0,0 +1,528 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+use super::admin::require_not_paused;
+use super::auction_management::validate_cancellation;
+use super::settlement::{auction_settlement_splits, pay_settlement_splits, record_seller_sale, SettlementPayment};
+
+/// Move every bundled asset out of its auction-owned vault. `accounts` holds
+/// one `(vault, destination)` pair per manifest item, in manifest order.
+fn transfer_bundle_items<'info>(
+    manifest: &BundleManifest,
+    accounts: &'info [AccountInfo<'info>],
+    recipient: Pubkey,
+    auction: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        accounts.len() == manifest.items.len() * 2,
+        ShadowProtocolError::BundleAccountMismatch
+    );
+    
+    for (item, pair) in manifest.items.iter().zip(accounts.chunks(2)) {
+        let vault_info = &pair[0];
+        let destination_info = &pair[1];
+        
+        require!(
+            vault_info.key() == item.vault,
+            ShadowProtocolError::BundleAccountMismatch
+        );
+        
+        let destination: Account<'info, TokenAccount> = Account::try_from(destination_info)?;
+        require!(
+            destination.mint == item.mint && destination.owner == recipient,
+            ShadowProtocolError::BundleAccountMismatch
+        );
+        
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: vault_info.clone(),
+                    to: destination_info.clone(),
+                    authority: auction.clone(),
+                },
+                signer_seeds,
+            ),
+            item.amount,
+        )?;
+    }
+    
+    Ok(())
+}
+
+/// Create a sealed-bid auction selling several SPL assets as one lot.
+/// Remaining accounts hold one `(mint, vault, creator_token_account)` triple
+/// per entry in `amounts`; each vault is the auction PDA's associated token
+/// account for that mint and is created here.
+pub fn create_bundle_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateBundleAuction<'info>>,
+    amounts: Vec<u64>,
+    duration: u64,
+    minimum_bid: u64,
+    reserve_price_encrypted: [u8; 32],
+    reserve_price_nonce: u128,
+) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    require_not_paused(protocol, PAUSE_CREATE, AuctionType::SealedBid)?;
+    
+    require!(
+        !amounts.is_empty() && amounts.len() <= MAX_BUNDLE_ITEMS,
+        ShadowProtocolError::InvalidBundle
+    );
+    
+    require!(
+        ctx.remaining_accounts.len() == amounts.len() * 3,
+        ShadowProtocolError::BundleAccountMismatch
+    );
+    
+    // Get the auction ID from protocol state
+    let auction_id = protocol.next_auction_id;
+    
+    // Increment next auction ID with overflow check
+    protocol.next_auction_id = protocol.next_auction_id
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    let clock = Clock::get()?;
+    let start_time = clock.unix_timestamp;
+    let end_time = start_time + duration as i64;
+    
+    require!(
+        duration as i64 <= MAX_AUCTION_DURATION,
+        ShadowProtocolError::AuctionDurationTooLong
+    );
+    
+    let creator = ctx.accounts.creator.key();
+    let auction_info = ctx.accounts.auction.to_account_info();
+    let mut items: Vec<BundleItem> = Vec::with_capacity(amounts.len());
+    
+    for (amount, triple) in amounts.iter().zip(ctx.remaining_accounts.chunks(3)) {
+        let mint_info = &triple[0];
+        let vault_info = &triple[1];
+        let creator_account_info = &triple[2];
+        
+        require!(*amount > 0, ShadowProtocolError::InvalidAssetAmount);
+        
+        let mint: Account<'info, Mint> = Account::try_from(mint_info)?;
+        require!(
+            items.iter().all(|item| item.mint != mint.key()),
+            ShadowProtocolError::InvalidBundle
+        );
+        
+        // Validate creator has sufficient assets
+        let creator_account: Account<'info, TokenAccount> = Account::try_from(creator_account_info)?;
+        require!(
+            creator_account.mint == mint.key() && creator_account.owner == creator,
+            ShadowProtocolError::BundleAccountMismatch
+        );
+        require!(
+            creator_account.amount >= *amount,
+            ShadowProtocolError::InsufficientFunds
+        );
+        
+        require!(
+            vault_info.key() == get_associated_token_address(&auction_info.key(), &mint.key()),
+            ShadowProtocolError::BundleAccountMismatch
+        );
+        
+        associated_token::create(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: ctx.accounts.creator.to_account_info(),
+                associated_token: vault_info.clone(),
+                authority: auction_info.clone(),
+                mint: mint_info.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+        
+        // Transfer exact asset amount to its vault
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: creator_account_info.clone(),
+                    to: vault_info.clone(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            *amount,
+        )?;
+        
+        items.push(BundleItem {
+            mint: mint.key(),
+            amount: *amount,
+            vault: vault_info.key(),
+        });
+    }
+    
+    let manifest = &mut ctx.accounts.bundle_manifest;
+    manifest.auction_id = auction_id;
+    manifest.items = items;
+    manifest.bump = ctx.bumps.bundle_manifest;
+    
+    let auction = &mut ctx.accounts.auction;
+    auction.auction_id = auction_id;
+    auction.creator = creator;
+    auction.asset_mint = Pubkey::default();
+    auction.asset_vault = Pubkey::default();
+    auction.asset_amount = 0;
+    auction.auction_type = AuctionType::SealedBid;
+    auction.status = AuctionStatus::Active;
+    auction.start_time = start_time;
+    auction.end_time = end_time;
+    auction.minimum_bid = minimum_bid;
+    auction.minimum_price_floor = 0;
+    auction.reserve_price_encrypted = reserve_price_encrypted;
+    auction.reserve_price_nonce = reserve_price_nonce;
+    auction.current_price = 0;
+    auction.price_decrease_rate = 0;
+    auction.bid_count = 0;
+    auction.winner = None;
+    auction.winning_amount = 0;
+    auction.settled_at = None;
+    auction.mpc_verification_hash = None;
+    auction.settlement_authorized = false;
+    auction.total_units = 1;
+    auction.units_allocated = 0;
+    auction.clearing_price = 0;
+    auction.bids_settled = 0;
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.pricing_rule = PricingRule::SecondPrice;
+    auction.budget = 0;
+    auction.bundle = Some(ctx.accounts.bundle_manifest.key());
+    auction.batch = None;
+    auction.protocol_fee_bps = protocol.protocol_fee;
+    auction.referral_fee_bps = protocol.referral_fee_bps;
+    auction.frozen = false;
+    auction.bids_closed = 0;
+    auction.gate = None;
+    auction.bid_bond = 0;
+    auction.nft = None;
+    auction.usd_pricing = None;
+    auction.timing_mode = TimingMode::Timestamp;
+    auction.keeper_bounty = 0;
+    auction.keeper = None;
+    auction.allocations_recorded = 0;
+    auction.nft_royalty_bps = None;
+    auction.version = AUCTION_ACCOUNT_VERSION;
+    auction.bump = ctx.bumps.auction;
+    
+    emit!(AuctionCreated {
+        auction_id,
+        creator,
+        asset_mint: Pubkey::default(),
+        auction_type: AuctionType::SealedBid,
+        start_time,
+        end_time,
+        minimum_bid,
+        timing_mode: TimingMode::Timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Settle a bundle auction: every bundled asset goes to the winner and the
+/// winner pays the creator, all in one transaction. The price is split like
+/// a single-asset sale (tiered protocol fee, referral fee), without creator
+/// royalties since a bundle has no single asset metadata. Remaining accounts
+/// hold one `(vault, winner_token_account)` pair per manifest item.
+pub fn execute_bundle_settlement<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteBundleSettlement<'info>>,
+    auction_id: u64,
+    winning_amount: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    
+    require_not_paused(protocol, PAUSE_SETTLE, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    // Check that settlement is authorized
+    require!(
+        auction.settlement_authorized,
+        ShadowProtocolError::SettlementNotAuthorized
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(
+        winning_amount > 0,
+        ShadowProtocolError::InvalidAssetAmount
+    );
+    
+    let winner = ctx.accounts.winner.key();
+    let settled_at = Clock::get()?.unix_timestamp;
+    auction.winner = Some(winner);
+    auction.winning_amount = winning_amount;
+    auction.status = AuctionStatus::Settled;
+    auction.settled_at = Some(settled_at);
+    
+    // Referral fee only applies when the winning bid names a referrer
+    let referrer = ctx.accounts.winning_bid.referrer;
+    let seller_stats = &mut ctx.accounts.seller_stats;
+    let splits = auction_settlement_splits(
+        auction,
+        protocol,
+        seller_stats,
+        referrer,
+        0,
+        winning_amount,
+    )?;
+    
+    // Transfer every bundled asset to winner
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let signer_seeds = &[&auction_seeds[..]];
+    
+    transfer_bundle_items(
+        &ctx.accounts.bundle_manifest,
+        ctx.remaining_accounts,
+        winner,
+        auction.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+    
+    let splits = pay_settlement_splits(
+        SettlementPayment {
+            source: ctx.accounts.winner_payment_account.to_account_info(),
+            authority: ctx.accounts.winner.to_account_info(),
+            seller: ctx.accounts.creator_payment_account.to_account_info(),
+            protocol_fee_vault: ctx.accounts.protocol_fee_account.to_account_info(),
+            referrer: ctx.accounts.referrer_payment_account.as_ref().map(|account| account.to_account_info()),
+            royalty_accounts: &[],
+            payment_mint: ctx.accounts.payment_mint.key(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        splits,
+        None,
+        referrer,
+        auction_id,
+        &[],
+    )?;
+    
+    record_seller_sale(seller_stats, auction.creator, winning_amount, ctx.bumps.seller_stats);
+    
+    emit!(AuctionSettled {
+        auction_id,
+        winner: Some(winner),
+        winning_amount,
+        splits: Some(splits),
+        settled_at,
+    });
+    
+    Ok(())
+}
+
+/// Cancel a bundle auction and return every bundled asset to the creator.
+/// Remaining accounts hold one `(vault, creator_token_account)` pair per
+/// manifest item.
+pub fn cancel_bundle_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CancelBundleAuction<'info>>,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    let signer = ctx.accounts.authority.key();
+    
+    validate_cancellation(auction, protocol, signer)?;
+    
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let signer_seeds = &[&auction_seeds[..]];
+    
+    transfer_bundle_items(
+        &ctx.accounts.bundle_manifest,
+        ctx.remaining_accounts,
+        auction.creator,
+        auction.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        signer_seeds,
+    )?;
+    
+    let clock = Clock::get()?;
+    auction.status = AuctionStatus::Cancelled;
+    
+    emit!(AuctionCancelled {
+        auction_id,
+        cancelled_by: signer,
+        cancelled_at: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateBundleAuction<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + AuctionAccount::INIT_SPACE,
+        seeds = [AUCTION_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BundleManifest::INIT_SPACE,
+        seeds = [BUNDLE_SEED, protocol_state.next_auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bundle_manifest: Account<'info, BundleManifest>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Mint bids are paid in
+    pub payment_mint: Account<'info, Mint>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct ExecuteBundleSettlement<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        constraint = auction.bundle == Some(bundle_manifest.key()) @ ShadowProtocolError::InvalidAuctionType
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [BUNDLE_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = bundle_manifest.bump
+    )]
+    pub bundle_manifest: Account<'info, BundleManifest>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Winner's payment account
+    #[account(
+        mut,
+        token::mint = auction.payment_mint,
+        token::authority = winner
+    )]
+    pub winner_payment_account: Account<'info, TokenAccount>,
+    
+    /// Creator's payment account
+    #[account(
+        mut,
+        token::mint = auction.payment_mint,
+        token::authority = auction.creator
+    )]
+    pub creator_payment_account: Account<'info, TokenAccount>,
+    
+    /// Payment token mint
+    #[account(address = auction.payment_mint @ ShadowProtocolError::InvalidPaymentMint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    /// Treasury PDA that owns the per-mint fee vaults
+    /// CHECK: PDA used only as the fee vault authority
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Treasury fee vault for the payment mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = winner,
+        token::mint = payment_mint,
+        token::authority = treasury,
+        seeds = [FEE_VAULT_SEED, payment_mint.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+    
+    /// Winning sealed bid, which carries the referrer
+    #[account(
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump = winning_bid.bump
+    )]
+    pub winning_bid: Account<'info, BidAccount>,
+    
+    /// Referrer's payment account (required when a referral fee is owed)
+    #[account(mut)]
+    pub referrer_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Seller's settled volume in the payment mint, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + SellerStats::INIT_SPACE,
+        seeds = [SELLER_STATS_SEED, auction.creator.as_ref(), payment_mint.key().as_ref()],
+        bump
+    )]
+    pub seller_stats: Account<'info, SellerStats>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CancelBundleAuction<'info> {
+    /// Auction creator or protocol authority
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        constraint = auction.bundle == Some(bundle_manifest.key()) @ ShadowProtocolError::InvalidAuctionType
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [BUNDLE_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = bundle_manifest.bump
+    )]
+    pub bundle_manifest: Account<'info, BundleManifest>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    pub token_program: Program<'info, Token>,
+}
\ No newline at end of file
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
-use crate::state::*;
-use crate::error::ShadowProtocolError;
-use crate::ComputationOutputs;
+use crate::state::SingleAuctionResult;
+// TODO: Re-enable after fixing Arcium callback integration
+// use anchor_spl::token::{Token, TokenAccount, Transfer, transfer};
+// use crate::state::*;
+// use crate::error::ShadowProtocolError;
+// use crate::ComputationOutputs;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AuctionResult {
@@ -13,7 +15,7 @@ pub struct AuctionResult {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct BatchResult {
-    pub results: Vec<AuctionResult>,
+    pub results: Vec<SingleAuctionResult>,
 }
 
 // TODO: Re-enable after fixing Arcium callback integration
@@ -44,6 +46,7 @@ pub fn handle_sealed_bid_settlement(
                 auction_id: auction.auction_id,
                 winner: Some(result.winner),
                 winning_amount: auction.winning_amount,
+                splits: None,
                 settled_at: Clock::get()?.unix_timestamp,
             });
             
@@ -101,6 +104,7 @@ pub fn handle_dutch_auction_result(
                     auction_id: auction.auction_id,
                     winner: auction.winner,
                     winning_amount: auction.winning_amount,
+                    splits: None,
                     settled_at: Clock::get()?.unix_timestamp,
                 });
                 
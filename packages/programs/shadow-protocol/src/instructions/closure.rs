@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, CloseAccount, close_account};
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+
+/// Close an empty token account owned by a program PDA, returning its rent
+fn close_empty_token_account<'info>(
+    account: AccountInfo<'info>,
+    amount: u64,
+    destination: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(amount == 0, ShadowProtocolError::AccountNotEmpty);
+    
+    close_account(CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account,
+            destination,
+            authority,
+        },
+        signer_seeds,
+    ))
+}
+
+fn is_finished(auction: &AuctionAccount) -> bool {
+    auction.status == AuctionStatus::Settled || auction.status == AuctionStatus::Cancelled
+}
+
+/// Close a settled or refunded bid and its escrow, returning the rent to the
+/// bidder. The auction must have finished.
+pub fn close_bid(ctx: Context<CloseBid>, auction_id: u64) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let bid = &ctx.accounts.bid;
+    
+    require!(is_finished(auction), ShadowProtocolError::InvalidAuctionStatus);
+    
+    require!(bid.settled, ShadowProtocolError::BidNotSettled);
+    
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let bidder_key = bid.bidder;
+    let bid_seeds = &[
+        BID_SEED,
+        auction_id_bytes.as_ref(),
+        bidder_key.as_ref(),
+        &[bid.bump],
+    ];
+    let signer_seeds = &[&bid_seeds[..]];
+    
+    match &ctx.accounts.bid_escrow {
+        Some(bid_escrow) => close_empty_token_account(
+            bid_escrow.to_account_info(),
+            bid_escrow.amount,
+            ctx.accounts.bidder.to_account_info(),
+            bid.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            signer_seeds,
+        )?,
+        // Native SOL escrows are closed when the collateral is refunded
+        None => {
+            let (sol_escrow, _) = Pubkey::find_program_address(
+                &[SOL_ESCROW_SEED, auction_id_bytes.as_ref(), bidder_key.as_ref()],
+                ctx.program_id,
+            );
+            require!(
+                bid.collateral_account == sol_escrow,
+                ShadowProtocolError::InvalidEscrowAccount
+            );
+        }
+    }
+    
+    auction.bids_closed = auction.bids_closed
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    // Closing frees a slot under the per-wallet open bid cap
+    if let Some(stats) = ctx.accounts.bidder_stats.as_mut() {
+        stats.active_bids = stats.active_bids.saturating_sub(1);
+    }
+    
+    emit!(BidClosed {
+        auction_id,
+        bidder: bidder_key,
+    });
+    
+    Ok(())
+}
+
+/// Close a settled batch auction order, returning the rent to the trader
+pub fn close_batch_order(ctx: Context<CloseBatchOrder>, auction_id: u64) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    
+    require!(is_finished(auction), ShadowProtocolError::InvalidAuctionStatus);
+    
+    require!(ctx.accounts.order.settled, ShadowProtocolError::BidNotSettled);
+    
+    auction.bids_closed = auction.bids_closed
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    emit!(BidClosed {
+        auction_id,
+        bidder: ctx.accounts.trader.key(),
+    });
+    
+    Ok(())
+}
+
+/// Close a finished auction and its vaults, returning the rent to the
+/// creator. Every bid (or batch order) must have been closed first so no
+/// bidder is left unable to claim a refund. Bundle auctions pass their empty
+/// item vaults as remaining accounts in manifest order.
+pub fn close_auction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseAuction<'info>>,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    
+    require!(is_finished(auction), ShadowProtocolError::InvalidAuctionStatus);
+    
+    // Dutch auctions settle without bid accounts
+    let expected_closed = if auction.auction_type == AuctionType::Dutch {
+        0
+    } else {
+        auction.bid_count
+    };
+    require!(
+        auction.bids_closed >= expected_closed,
+        ShadowProtocolError::OpenBidsRemaining
+    );
+    
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let signer_seeds = &[&auction_seeds[..]];
+    
+    let creator_info = ctx.accounts.creator.to_account_info();
+    let auction_info = auction.to_account_info();
+    let token_program_info = ctx.accounts.token_program.to_account_info();
+    
+    // Bundles have no single asset vault
+    if auction.asset_vault != Pubkey::default() {
+        let asset_vault = ctx.accounts.asset_vault
+            .as_ref()
+            .ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        close_empty_token_account(
+            asset_vault.to_account_info(),
+            asset_vault.amount,
+            creator_info.clone(),
+            auction_info.clone(),
+            token_program_info.clone(),
+            signer_seeds,
+        )?;
+    }
+    
+    if auction.auction_type == AuctionType::Batch {
+        let quote_vault = ctx.accounts.quote_vault
+            .as_ref()
+            .ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        close_empty_token_account(
+            quote_vault.to_account_info(),
+            quote_vault.amount,
+            creator_info.clone(),
+            auction_info.clone(),
+            token_program_info.clone(),
+            signer_seeds,
+        )?;
+    }
+    
+    if auction.bundle.is_some() {
+        let manifest = ctx.accounts.bundle_manifest
+            .as_ref()
+            .ok_or(ShadowProtocolError::BundleAccountMismatch)?;
+        
+        require!(
+            ctx.remaining_accounts.len() == manifest.items.len(),
+            ShadowProtocolError::BundleAccountMismatch
+        );
+        
+        for (item, vault_info) in manifest.items.iter().zip(ctx.remaining_accounts.iter()) {
+            require!(
+                vault_info.key() == item.vault,
+                ShadowProtocolError::BundleAccountMismatch
+            );
+            let vault: Account<'info, TokenAccount> = Account::try_from(vault_info)?;
+            close_empty_token_account(
+                vault_info.clone(),
+                vault.amount,
+                creator_info.clone(),
+                auction_info.clone(),
+                token_program_info.clone(),
+                signer_seeds,
+            )?;
+        }
+        
+        manifest.close(creator_info.clone())?;
+    }
+    
+    if let Some(metadata) = &ctx.accounts.auction_metadata {
+        metadata.close(creator_info.clone())?;
+    }
+    
+    emit!(AuctionClosed {
+        auction_id,
+        creator: auction.creator,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CloseBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        mut,
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        has_one = bidder,
+        close = bidder
+    )]
+    pub bid: Account<'info, BidAccount>,
+    
+    /// Bid escrow, closed alongside the bid (omitted for native SOL bids)
+    #[account(
+        mut,
+        seeds = [BID_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid_escrow: Option<Account<'info, TokenAccount>>,
+    
+    /// Bidder's open bid count (absent for bids opened before the cap existed)
+    #[account(
+        mut,
+        seeds = [BIDDER_STATS_SEED, bidder.key().as_ref()],
+        bump = bidder_stats.bump
+    )]
+    pub bidder_stats: Option<Account<'info, BidderStats>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CloseBatchOrder<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        mut,
+        seeds = [ORDER_SEED, auction_id.to_le_bytes().as_ref(), trader.key().as_ref()],
+        bump = order.bump,
+        has_one = trader,
+        close = trader
+    )]
+    pub order: Account<'info, BatchOrder>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CloseAuction<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized,
+        close = creator
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    /// Asset vault (omitted for bundle auctions)
+    #[account(
+        mut,
+        address = auction.asset_vault
+    )]
+    pub asset_vault: Option<Account<'info, TokenAccount>>,
+    
+    /// Quote vault (batch auctions only)
+    #[account(
+        mut,
+        seeds = [QUOTE_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub quote_vault: Option<Account<'info, TokenAccount>>,
+    
+    /// Bundle manifest (bundle auctions only)
+    #[account(
+        mut,
+        seeds = [BUNDLE_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = bundle_manifest.bump
+    )]
+    pub bundle_manifest: Option<Account<'info, BundleManifest>>,
+    
+    /// Listing metadata, closed with the auction when present
+    #[account(
+        mut,
+        seeds = [AUCTION_METADATA_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction_metadata.bump
+    )]
+    pub auction_metadata: Option<Account<'info, AuctionMetadata>>,
+    
+    pub token_program: Program<'info, Token>,
+}
\ No newline at end of file
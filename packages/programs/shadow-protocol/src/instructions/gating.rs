@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+
+/// Leaf committed to in allowlist Merkle trees for a bidder
+fn allowlist_leaf(bidder: &Pubkey) -> [u8; 32] {
+    hashv(&[bidder.as_ref()]).to_bytes()
+}
+
+/// Verify a Merkle proof using sorted-pair SHA-256 hashing
+fn verify_allowlist_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            hashv(&[&node, sibling]).to_bytes()
+        } else {
+            hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    
+    computed == *root
+}
+
+/// Check a bidder against the auction's gate, if it has one
+pub(crate) fn require_bidder_allowed(
+    auction: &AuctionAccount,
+    bidder: &Pubkey,
+    allowlist_proof: &[[u8; 32]],
+    gate_token_account: Option<&Account<TokenAccount>>,
+    gate_attester: Option<&Signer>,
+) -> Result<()> {
+    let Some(gate) = auction.gate else {
+        return Ok(());
+    };
+    
+    let allowed = match gate {
+        BidderGate::Merkle { root } => {
+            allowlist_proof.len() <= MAX_ALLOWLIST_PROOF_LEN
+                && verify_allowlist_proof(&root, allowlist_leaf(bidder), allowlist_proof)
+        }
+        BidderGate::TokenHolding { mint, min_amount } => gate_token_account
+            .is_some_and(|account| {
+                account.owner == *bidder && account.mint == mint && account.amount >= min_amount
+            }),
+        BidderGate::Attester { attester } => gate_attester
+            .is_some_and(|signer| signer.key() == attester),
+    };
+    
+    require!(allowed, ShadowProtocolError::BidderNotAllowed);
+    
+    Ok(())
+}
+
+/// Restrict (or reopen) bidding on an auction. The gate can only change
+/// before the first bid, so create the auction and set its gate in the same
+/// transaction to keep it closed from the start.
+pub fn set_auction_gate(
+    ctx: Context<UpdateBidRules>,
+    auction_id: u64,
+    gate: Option<BidderGate>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    
+    require!(
+        auction.auction_type == AuctionType::SealedBid || auction.auction_type == AuctionType::Dutch,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Active && auction.bid_count == 0,
+        ShadowProtocolError::InvalidAuctionGate
+    );
+    
+    auction.gate = gate;
+    
+    emit!(AuctionGateUpdated {
+        auction_id,
+        gate,
+    });
+    
+    Ok(())
+}
+
+/// Set the non-refundable bond each bidder pays the seller. Like the gate,
+/// it can only change before the first bid.
+pub fn set_bid_bond(ctx: Context<UpdateBidRules>, auction_id: u64, bid_bond: u64) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    
+    // Only auctions that open bid accounts collect bonds
+    require!(
+        auction.auction_type != AuctionType::Dutch && auction.auction_type != AuctionType::Batch,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Active && auction.bid_count == 0,
+        ShadowProtocolError::InvalidAuctionGate
+    );
+    
+    auction.bid_bond = bid_bond;
+    
+    emit!(BidBondUpdated {
+        auction_id,
+        bid_bond,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct UpdateBidRules<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+}
\ No newline at end of file
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+
+/// Escrow a SOL bounty on the auction account for whoever cranks its
+/// settlement. Send it alongside auction creation; top-ups are allowed while
+/// the auction is active. Unclaimed bounties return with `close_auction`.
+pub fn fund_keeper_bounty(
+    ctx: Context<FundKeeperBounty>,
+    auction_id: u64,
+    amount: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    
+    require!(
+        amount > 0 && auction.status == AuctionStatus::Active && auction.keeper.is_none(),
+        ShadowProtocolError::InvalidKeeperBounty
+    );
+    
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: auction.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    auction.keeper_bounty = auction.keeper_bounty
+        .checked_add(amount)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    emit!(KeeperBountyFunded {
+        auction_id,
+        amount,
+        total_bounty: auction.keeper_bounty,
+    });
+    
+    Ok(())
+}
+
+/// Pays the auction's keeper bounty to `keeper`. The bounty is zeroed and
+/// the keeper recorded, so later crank steps on the same auction pay nothing.
+pub(crate) fn pay_keeper_bounty<'info>(
+    auction: &mut Account<'info, AuctionAccount>,
+    keeper: &AccountInfo<'info>,
+) -> Result<()> {
+    let amount = auction.keeper_bounty;
+    if amount == 0 || auction.keeper.is_some() {
+        return Ok(());
+    }
+    
+    auction.keeper_bounty = 0;
+    auction.keeper = Some(keeper.key());
+    auction.sub_lamports(amount)?;
+    keeper.add_lamports(amount)?;
+    
+    emit!(KeeperRewarded {
+        auction_id: auction.auction_id,
+        keeper: keeper.key(),
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct FundKeeperBounty<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::associated_token;
+use anchor_spl::token::Mint;
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+
+// Metaplex metadata account discriminator (Key::MetadataV1)
+const METADATA_V1_KEY: u8 = 4;
+
+// TokenStandard::ProgrammableNonFungible
+const TOKEN_STANDARD_PROGRAMMABLE_NON_FUNGIBLE: u8 = 4;
+
+// MetadataInstruction::Transfer
+const TRANSFER_INSTRUCTION: u8 = 49;
+
+/// Creator entry read from Metaplex metadata
+pub(crate) struct MetadataCreator {
+    pub address: Pubkey,
+    pub share: u8,
+}
+
+/// Royalty-relevant fields of a Metaplex metadata account
+pub(crate) struct AssetMetadata {
+    pub seller_fee_basis_points: u16,
+    pub creators: Vec<MetadataCreator>,
+    pub token_standard: Option<u8>,
+    /// Collection key, only when the collection is verified
+    pub verified_collection: Option<Pubkey>,
+}
+
+impl AssetMetadata {
+    pub fn is_programmable(&self) -> bool {
+        self.token_standard == Some(TOKEN_STANDARD_PROGRAMMABLE_NON_FUNGIBLE)
+    }
+}
+
+/// Metaplex metadata address for `mint`
+fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Royalty source for a settlement. The metadata address is always passed,
+/// so the payer cannot skip royalties by leaving it out; an empty account
+/// at that address means the asset has no metadata and pays none.
+pub(crate) fn read_royalty_metadata(metadata: &AccountInfo, mint: &Pubkey) -> Result<Option<AssetMetadata>> {
+    if metadata.owner == &TOKEN_METADATA_PROGRAM_ID {
+        return read_asset_metadata(metadata, mint).map(Some);
+    }
+    
+    require!(
+        metadata.key() == metadata_address(mint) && metadata.data_is_empty(),
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    
+    Ok(None)
+}
+
+/// Reads the Metaplex metadata account for `mint`. Only the fixed prefix of
+/// the account is decoded, so no Metaplex crate dependency is needed.
+pub(crate) fn read_asset_metadata(metadata: &AccountInfo, mint: &Pubkey) -> Result<AssetMetadata> {
+    require!(
+        metadata.owner == &TOKEN_METADATA_PROGRAM_ID,
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    
+    require!(
+        metadata.key() == metadata_address(mint),
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    
+    let data = metadata.try_borrow_data()?;
+    let mut reader = MetadataReader { data: &data, offset: 0 };
+    
+    require!(
+        reader.read_u8()? == METADATA_V1_KEY,
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    
+    // Update authority, then the mint itself
+    reader.skip(32)?;
+    require!(
+        reader.read_pubkey()? == *mint,
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    
+    // Name, symbol and URI
+    for _ in 0..3 {
+        let len = reader.read_u32()? as usize;
+        reader.skip(len)?;
+    }
+    
+    let seller_fee_basis_points = reader.read_u16()?;
+    require!(
+        seller_fee_basis_points <= 10000,
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    
+    let mut creators = Vec::new();
+    if reader.read_u8()? == 1 {
+        let count = reader.read_u32()? as usize;
+        require!(
+            count <= MAX_METADATA_CREATORS,
+            ShadowProtocolError::InvalidMetadataAccount
+        );
+        for _ in 0..count {
+            let address = reader.read_pubkey()?;
+            // Verification flag; royalties go to every listed creator
+            reader.skip(1)?;
+            let share = reader.read_u8()?;
+            creators.push(MetadataCreator { address, share });
+        }
+    }
+    
+    // Primary sale and mutability flags
+    reader.skip(2)?;
+    reader.read_option_u8()?;
+    let token_standard = reader.read_option_u8()?;
+    
+    let mut verified_collection = None;
+    if reader.read_u8()? == 1 {
+        let verified = reader.read_u8()? == 1;
+        let key = reader.read_pubkey()?;
+        if verified {
+            verified_collection = Some(key);
+        }
+    }
+    
+    Ok(AssetMetadata {
+        seller_fee_basis_points,
+        creators,
+        token_standard,
+        verified_collection,
+    })
+}
+
+/// Checks that `mint` is a single NFT and, when requested, a verified member
+/// of the required collection, and that its royalty leaves room for the
+/// auction's `fee_bps`. Returns the listing and the royalty to snapshot on
+/// the auction, so a later metadata update cannot change what the sale pays.
+pub(crate) fn verify_nft_listing(
+    mint: &Account<Mint>,
+    asset_amount: u64,
+    metadata: Option<&UncheckedAccount>,
+    options: &NftOptions,
+    fee_bps: u16,
+) -> Result<(NftListing, u16)> {
+    require!(
+        mint.supply == 1 && mint.decimals == 0 && asset_amount == 1,
+        ShadowProtocolError::NotANonFungibleToken
+    );
+    
+    let metadata = metadata.ok_or(ShadowProtocolError::InvalidMetadataAccount)?;
+    let asset_metadata = read_asset_metadata(metadata, &mint.key())?;
+    
+    if let Some(collection) = options.required_collection {
+        require!(
+            asset_metadata.verified_collection == Some(collection),
+            ShadowProtocolError::CollectionNotVerified
+        );
+    }
+    
+    // Royalties on top of the protocol and referral fees must fit the price
+    require!(
+        asset_metadata.seller_fee_basis_points as u32 + fee_bps as u32 <= 10000,
+        ShadowProtocolError::SettlementSplitsExceedPrice
+    );
+    
+    let listing = NftListing {
+        collection: options.required_collection,
+        programmable: asset_metadata.is_programmable(),
+    };
+    
+    Ok((listing, asset_metadata.seller_fee_basis_points))
+}
+
+/// Accounts for moving a programmable NFT with token-metadata `TransferV1`.
+///
+/// `extra` holds, in order: the token-metadata program, the master edition,
+/// the source and destination token records, the instructions sysvar, the
+/// associated token program and, for NFTs with a rule set, the authorization
+/// rules program and rule set account.
+pub(crate) struct NftTransferAccounts<'a, 'info> {
+    pub token: AccountInfo<'info>,
+    pub token_owner: AccountInfo<'info>,
+    pub destination: AccountInfo<'info>,
+    pub destination_owner: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub extra: &'a [AccountInfo<'info>],
+}
+
+/// Transfers a programmable NFT. pNFT token accounts stay frozen, so the SPL
+/// transfer used for other assets would fail; token-metadata thaws, moves
+/// and re-freezes the token while enforcing the collection's rule set.
+pub(crate) fn transfer_programmable_nft(
+    accounts: NftTransferAccounts,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require!(
+        accounts.extra.len() == 6 || accounts.extra.len() == 8,
+        ShadowProtocolError::InvalidNftTransferAccounts
+    );
+    
+    let program = &accounts.extra[0];
+    let edition = &accounts.extra[1];
+    let owner_token_record = &accounts.extra[2];
+    let destination_token_record = &accounts.extra[3];
+    let sysvar_instructions = &accounts.extra[4];
+    let associated_token_program = &accounts.extra[5];
+    
+    require!(
+        program.key() == TOKEN_METADATA_PROGRAM_ID
+            && sysvar_instructions.key() == sysvar::instructions::ID
+            && associated_token_program.key() == associated_token::ID,
+        ShadowProtocolError::InvalidNftTransferAccounts
+    );
+    
+    // Omitted optional accounts are passed as the token-metadata program id
+    let (rules_program, rules) = match accounts.extra.get(6..8) {
+        Some([rules_program, rules]) => (rules_program, rules),
+        _ => (program, program),
+    };
+    
+    let metas = vec![
+        AccountMeta::new(accounts.token.key(), false),
+        AccountMeta::new_readonly(accounts.token_owner.key(), false),
+        AccountMeta::new(accounts.destination.key(), false),
+        AccountMeta::new_readonly(accounts.destination_owner.key(), false),
+        AccountMeta::new_readonly(accounts.mint.key(), false),
+        AccountMeta::new(accounts.metadata.key(), false),
+        AccountMeta::new_readonly(edition.key(), false),
+        AccountMeta::new(owner_token_record.key(), false),
+        AccountMeta::new(destination_token_record.key(), false),
+        AccountMeta::new_readonly(accounts.authority.key(), true),
+        AccountMeta::new(accounts.payer.key(), true),
+        AccountMeta::new_readonly(accounts.system_program.key(), false),
+        AccountMeta::new_readonly(sysvar_instructions.key(), false),
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+        AccountMeta::new_readonly(associated_token_program.key(), false),
+        AccountMeta::new_readonly(rules_program.key(), false),
+        AccountMeta::new_readonly(rules.key(), false),
+    ];
+    
+    // TransferArgs::V1 { amount, authorization_data: None }
+    let mut data = vec![TRANSFER_INSTRUCTION, 0];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(0);
+    
+    let instruction = Instruction {
+        program_id: TOKEN_METADATA_PROGRAM_ID,
+        accounts: metas,
+        data,
+    };
+    
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.token,
+            accounts.token_owner,
+            accounts.destination,
+            accounts.destination_owner,
+            accounts.mint,
+            accounts.metadata,
+            edition.clone(),
+            owner_token_record.clone(),
+            destination_token_record.clone(),
+            accounts.authority,
+            accounts.payer,
+            accounts.system_program,
+            sysvar_instructions.clone(),
+            accounts.token_program,
+            associated_token_program.clone(),
+            rules_program.clone(),
+            rules.clone(),
+            program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    
+    Ok(())
+}
+
+struct MetadataReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl MetadataReader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        let end = self.offset
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or(ShadowProtocolError::InvalidMetadataAccount)?;
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+    
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.take(len).map(|_| ())
+    }
+    
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+    
+    fn read_u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+    
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+    
+    fn read_option_u8(&mut self) -> Result<Option<u8>> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            _ => self.read_u8().map(Some),
+        }
+    }
+    
+    fn read_pubkey(&mut self) -> Result<Pubkey> {
+        let bytes = self.take(32)?;
+        Pubkey::try_from(bytes).map_err(|_| error!(ShadowProtocolError::InvalidMetadataAccount))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,407 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_lang::Discriminator;
+use anchor_spl::token::Mint;
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+use super::admin::require_role;
+
+/// Grow a program account to `space` bytes in place, topping up rent from
+/// the payer. The new tail is zero-filled, which every appended field
+/// decodes from.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    discriminator: &[u8],
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    require!(
+        account.owner == &crate::ID,
+        ShadowProtocolError::InvalidMigrationAccount
+    );
+    
+    require!(
+        account.try_borrow_data()?.starts_with(discriminator),
+        ShadowProtocolError::InvalidMigrationAccount
+    );
+    
+    if account.data_len() < space {
+        let rent_shortfall = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(account.lamports());
+        
+        if rent_shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.to_account_info(),
+                    Transfer {
+                        from: payer.to_account_info(),
+                        to: account.clone(),
+                    },
+                ),
+                rent_shortfall,
+            )?;
+        }
+        
+        account.resize(space)?;
+    }
+    
+    Ok(())
+}
+
+/// Decode an account grown by [`grow_account`]
+fn load<T: AccountDeserialize>(account: &AccountInfo) -> Result<T> {
+    let data = account.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..])
+}
+
+/// Write back a migrated account. Bytes past the serialized value are
+/// cleared so fields appended by a later version start from zero even
+/// where an `Option` shrank since the account was last written.
+fn store<T: AccountSerialize>(
+    account: &AccountInfo,
+    value: &T,
+    from_version: u8,
+    to_version: u8,
+    migrated_by: Pubkey,
+) -> Result<()> {
+    let mut serialized = Vec::new();
+    value.try_serialize(&mut serialized)?;
+    
+    let mut data = account.try_borrow_mut_data()?;
+    require!(
+        serialized.len() <= data.len(),
+        ShadowProtocolError::InvalidMigrationAccount
+    );
+    data[..serialized.len()].copy_from_slice(&serialized);
+    data[serialized.len()..].fill(0);
+    
+    emit!(AccountMigrated {
+        account: account.key(),
+        from_version,
+        to_version,
+        migrated_by,
+    });
+    
+    Ok(())
+}
+
+/// Migrate the protocol state to the current layout (protocol authority only)
+pub fn migrate_protocol_state(ctx: Context<MigrateProtocolState>) -> Result<()> {
+    let protocol_info = ctx.accounts.protocol_state.to_account_info();
+    
+    grow_account(
+        &protocol_info,
+        ProtocolState::DISCRIMINATOR,
+        8 + ProtocolState::INIT_SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+    
+    let mut protocol: ProtocolState = load(&protocol_info)?;
+    
+    require!(
+        ctx.accounts.authority.key() == protocol.authority,
+        ShadowProtocolError::Unauthorized
+    );
+    
+    let from_version = protocol.version;
+    require!(
+        from_version < PROTOCOL_STATE_VERSION,
+        ShadowProtocolError::AccountAlreadyMigrated
+    );
+    
+    if from_version == 0 {
+        // The original layout had a single pause switch and no batch ids,
+        // fee timelock or bid limits
+        protocol.pause_flags = if protocol.paused { PAUSE_ALL } else { 0 };
+        protocol.next_batch_id = 1;
+        protocol.fee_update_delay = DEFAULT_FEE_UPDATE_DELAY;
+        protocol.bid_deposit_lamports = DEFAULT_BID_DEPOSIT_LAMPORTS;
+        protocol.max_active_bids_per_wallet = DEFAULT_MAX_ACTIVE_BIDS_PER_WALLET;
+    }
+    protocol.version = PROTOCOL_STATE_VERSION;
+    
+    store(
+        &protocol_info,
+        &protocol,
+        from_version,
+        PROTOCOL_STATE_VERSION,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    msg!("Protocol state migrated from v{} to v{}", from_version, PROTOCOL_STATE_VERSION);
+    
+    Ok(())
+}
+
+/// Migrate an auction account to the current layout. The protocol state
+/// must be migrated first.
+pub fn migrate_auction(ctx: Context<MigrateAuction>, auction_id: u64) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::UpgradeAuthority,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    let auction_info = ctx.accounts.auction.to_account_info();
+    
+    grow_account(
+        &auction_info,
+        AuctionAccount::DISCRIMINATOR,
+        8 + AuctionAccount::INIT_SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+    
+    let mut auction: AuctionAccount = load(&auction_info)?;
+    
+    let from_version = auction.version;
+    require!(
+        from_version < AUCTION_ACCOUNT_VERSION,
+        ShadowProtocolError::AccountAlreadyMigrated
+    );
+    
+    if from_version == 0 {
+        // Original auctions sold a single lot, paid the live protocol fee and
+        // left the payment mint to settlement
+        auction.payment_mint = ctx.accounts.payment_mint
+            .as_ref()
+            .ok_or(ShadowProtocolError::InvalidPaymentMint)?
+            .key();
+        auction.total_units = 1;
+        auction.protocol_fee_bps = protocol.protocol_fee;
+        auction.referral_fee_bps = protocol.referral_fee_bps;
+    }
+    auction.version = AUCTION_ACCOUNT_VERSION;
+    
+    store(
+        &auction_info,
+        &auction,
+        from_version,
+        AUCTION_ACCOUNT_VERSION,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    msg!("Auction {} migrated from v{} to v{}", auction_id, from_version, AUCTION_ACCOUNT_VERSION);
+    
+    Ok(())
+}
+
+/// Migrate a bid account to the current layout
+pub fn migrate_bid(ctx: Context<MigrateBid>, auction_id: u64, bidder: Pubkey) -> Result<()> {
+    require_role(
+        &ctx.accounts.protocol_state,
+        ctx.accounts.roles.as_deref(),
+        Role::UpgradeAuthority,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    let bid_info = ctx.accounts.bid.to_account_info();
+    
+    grow_account(
+        &bid_info,
+        BidAccount::DISCRIMINATOR,
+        8 + BidAccount::INIT_SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+    
+    let mut bid: BidAccount = load(&bid_info)?;
+    
+    let from_version = bid.version;
+    require!(
+        from_version < BID_ACCOUNT_VERSION,
+        ShadowProtocolError::AccountAlreadyMigrated
+    );
+    
+    bid.version = BID_ACCOUNT_VERSION;
+    
+    store(
+        &bid_info,
+        &bid,
+        from_version,
+        BID_ACCOUNT_VERSION,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    msg!("Bid of {} on auction {} migrated from v{} to v{}",
+         bidder, auction_id, from_version, BID_ACCOUNT_VERSION);
+    
+    Ok(())
+}
+
+/// Migrate a batch settlement account to the current layout. Batches from
+/// the original layout were seeded by their creator rather than batch id.
+pub fn migrate_batch(ctx: Context<MigrateBatch>, batch_id: u64) -> Result<()> {
+    require_role(
+        &ctx.accounts.protocol_state,
+        ctx.accounts.roles.as_deref(),
+        Role::UpgradeAuthority,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    let batch_info = ctx.accounts.batch.to_account_info();
+    
+    grow_account(
+        &batch_info,
+        BatchSettlement::DISCRIMINATOR,
+        8 + BatchSettlement::INIT_SPACE,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+    )?;
+    
+    let mut batch: BatchSettlement = load(&batch_info)?;
+    
+    require!(
+        batch.batch_id == batch_id,
+        ShadowProtocolError::InvalidMigrationAccount
+    );
+    
+    let batch_id_bytes = batch_id.to_le_bytes();
+    let seed: &[u8] = if batch.version == 0 {
+        batch.creator.as_ref()
+    } else {
+        batch_id_bytes.as_ref()
+    };
+    let expected = Pubkey::create_program_address(&[BATCH_SEED, seed, &[batch.bump]], &crate::ID)
+        .map_err(|_| error!(ShadowProtocolError::InvalidMigrationAccount))?;
+    require!(
+        batch_info.key() == expected,
+        ShadowProtocolError::InvalidMigrationAccount
+    );
+    
+    let from_version = batch.version;
+    require!(
+        from_version < BATCH_SETTLEMENT_VERSION,
+        ShadowProtocolError::AccountAlreadyMigrated
+    );
+    
+    batch.version = BATCH_SETTLEMENT_VERSION;
+    
+    store(
+        &batch_info,
+        &batch,
+        from_version,
+        BATCH_SETTLEMENT_VERSION,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    msg!("Batch {} migrated from v{} to v{}", batch_id, from_version, BATCH_SETTLEMENT_VERSION);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateProtocolState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: May hold an older layout; owner, discriminator and authority are
+    /// checked in the handler after growing it
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump
+    )]
+    pub protocol_state: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct MigrateAuction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    /// CHECK: May hold an older layout; owner and discriminator are checked in
+    /// the handler
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub auction: UncheckedAccount<'info>,
+    
+    /// Mint the auction settles in (required when migrating the original
+    /// layout, which did not record one)
+    pub payment_mint: Option<Account<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64, bidder: Pubkey)]
+pub struct MigrateBid<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    /// CHECK: May hold an older layout; owner and discriminator are checked in
+    /// the handler
+    #[account(
+        mut,
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), bidder.as_ref()],
+        bump
+    )]
+    pub bid: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(batch_id: u64)]
+pub struct MigrateBatch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    /// CHECK: May hold an older layout, whose seeds differ; owner,
+    /// discriminator, batch id and address are checked in the handler
+    #[account(mut)]
+    pub batch: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -1,13 +1,34 @@
-use anchor_lang::prelude::*;
-
 pub mod auction_management;
 pub mod bidding;
 pub mod settlement;
 pub mod admin;
 pub mod callbacks;
+pub mod bundle;
+pub mod batch_auction;
+pub mod multisig;
+pub mod treasury;
+pub mod metadata;
+pub mod closure;
+pub mod migration;
+pub mod gating;
+pub mod auction_metadata;
+pub mod native_sol;
+pub mod oracle;
+pub mod keeper;
 
 pub use auction_management::*;
 pub use bidding::*;
 pub use settlement::*;
 pub use admin::*;
-pub use callbacks::*;
\ No newline at end of file
+pub use callbacks::*;
+pub use bundle::*;
+pub use batch_auction::*;
+pub use multisig::*;
+pub use treasury::*;
+pub use closure::*;
+pub use migration::*;
+pub use gating::*;
+pub use auction_metadata::*;
+pub use native_sol::*;
+pub use oracle::*;
+pub use keeper::*;
\ No newline at end of file
@@ -0,0 +1,469 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+use super::admin::{
+    queue_protocol_fee, queue_fee_recipient, queue_referral_fee, queue_fee_tiers,
+    change_fee_update_delay, change_bid_limits, add_role_grant, remove_role_grant,
+};
+#[cfg(feature = "mock-oracle")]
+use super::oracle::publish_mock_price;
+
+/// Hand control of sensitive admin actions to an M-of-N multisig. Once
+/// configured, fee, referral fee, fee tier and fee recipient changes, the
+/// fee update delay, bid limits, role grants, mock prices (in `mock-oracle`
+/// builds), authority
+/// transfers, unpausing, unfreezing auctions and changes to the multisig
+/// itself can only happen through executed proposals.
+pub fn configure_multisig(
+    ctx: Context<ConfigureMultisig>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol_state;
+    
+    require!(
+        ctx.accounts.authority.key() == protocol.authority,
+        ShadowProtocolError::Unauthorized
+    );
+    
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigAlreadyConfigured
+    );
+    
+    require!(
+        !members.is_empty() && members.len() <= MAX_MULTISIG_MEMBERS,
+        ShadowProtocolError::InvalidMultisigConfig
+    );
+    
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        ShadowProtocolError::InvalidMultisigConfig
+    );
+    
+    // Duplicate members would let one key count twice
+    for (i, member) in members.iter().enumerate() {
+        require!(
+            !members[..i].contains(member),
+            ShadowProtocolError::InvalidMultisigConfig
+        );
+    }
+    
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.members = members;
+    multisig.threshold = threshold;
+    multisig.next_proposal_id = 1;
+    multisig.bump = ctx.bumps.multisig;
+    
+    protocol.admin_multisig = Some(multisig.key());
+    
+    emit!(MultisigConfigured {
+        multisig: multisig.key(),
+        member_count: multisig.members.len() as u8,
+        threshold,
+    });
+    
+    msg!("Admin multisig configured: {} of {}", threshold, multisig.members.len());
+    
+    Ok(())
+}
+
+/// Propose an admin action. The proposer's approval is counted immediately.
+pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposer = ctx.accounts.proposer.key();
+    let clock = Clock::get()?;
+    
+    require!(
+        multisig.members.contains(&proposer),
+        ShadowProtocolError::NotMultisigMember
+    );
+    
+    match &action {
+        AdminAction::SetProtocolFee { new_fee } => {
+            require!(
+                *new_fee <= MAX_PROTOCOL_FEE,
+                ShadowProtocolError::InvalidProtocolFee
+            );
+        }
+        AdminAction::SetReferralFee { referral_fee_bps } => {
+            require!(
+                *referral_fee_bps <= MAX_REFERRAL_FEE,
+                ShadowProtocolError::InvalidReferralFee
+            );
+        }
+        AdminAction::SetFeeTiers { fee_tiers } => {
+            require!(
+                fee_tiers.len() <= MAX_FEE_TIERS,
+                ShadowProtocolError::InvalidFeeTiers
+            );
+        }
+        AdminAction::ChangeThreshold { threshold } => {
+            require!(*threshold > 0, ShadowProtocolError::InvalidMultisigConfig);
+        }
+        AdminAction::SetFeeUpdateDelay { new_delay } => {
+            require!(
+                (MIN_FEE_UPDATE_DELAY..=MAX_FEE_UPDATE_DELAY).contains(new_delay),
+                ShadowProtocolError::InvalidTimelockDelay
+            );
+        }
+        AdminAction::SetBidLimits { bid_deposit_lamports, .. } => {
+            require!(
+                *bid_deposit_lamports <= MAX_BID_DEPOSIT_LAMPORTS,
+                ShadowProtocolError::InvalidBidLimits
+            );
+        }
+        _ => {}
+    }
+    
+    let proposal_id = multisig.next_proposal_id;
+    
+    multisig.next_proposal_id = multisig.next_proposal_id
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.proposal_id = proposal_id;
+    proposal.proposer = proposer;
+    proposal.action = action.clone();
+    proposal.approvals = vec![proposer];
+    proposal.executed = false;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.expires_at = clock.unix_timestamp
+        .checked_add(PROPOSAL_LIFETIME)
+        .ok_or(ShadowProtocolError::InvalidTimestamp)?;
+    
+    emit!(ProposalCreated {
+        proposal_id,
+        proposer,
+        action,
+    });
+    
+    Ok(())
+}
+
+/// Approve a pending proposal
+pub fn approve_proposal(ctx: Context<ApproveProposal>, proposal_id: u64) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let approver = ctx.accounts.approver.key();
+    
+    require!(
+        multisig.members.contains(&approver),
+        ShadowProtocolError::NotMultisigMember
+    );
+    
+    require!(!proposal.executed, ShadowProtocolError::ProposalAlreadyExecuted);
+    
+    require!(
+        Clock::get()?.unix_timestamp <= proposal.expires_at,
+        ShadowProtocolError::ProposalExpired
+    );
+    
+    require!(
+        !proposal.approvals.contains(&approver),
+        ShadowProtocolError::ProposalAlreadyApproved
+    );
+    
+    proposal.approvals.push(approver);
+    
+    emit!(ProposalApproved {
+        proposal_id,
+        approver,
+        approvals: proposal.approvals.len() as u8,
+    });
+    
+    Ok(())
+}
+
+/// Execute a proposal that has reached the approval threshold
+pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_id: u64) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let proposal = &mut ctx.accounts.proposal;
+    let protocol = &mut ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    require!(
+        multisig.members.contains(&ctx.accounts.executor.key()),
+        ShadowProtocolError::NotMultisigMember
+    );
+    
+    require!(!proposal.executed, ShadowProtocolError::ProposalAlreadyExecuted);
+    
+    require!(
+        clock.unix_timestamp <= proposal.expires_at,
+        ShadowProtocolError::ProposalExpired
+    );
+    
+    // Approvals from members removed since they approved no longer count
+    let approvals = proposal.approvals
+        .iter()
+        .filter(|approver| multisig.members.contains(approver))
+        .count();
+    
+    require!(
+        approvals >= multisig.threshold as usize,
+        ShadowProtocolError::ProposalThresholdNotMet
+    );
+    
+    match proposal.action.clone() {
+        // Fee changes still wait out the fee update timelock
+        AdminAction::SetProtocolFee { new_fee } => {
+            queue_protocol_fee(protocol, new_fee)?;
+        }
+        AdminAction::SetFeeRecipient { new_recipient } => {
+            queue_fee_recipient(protocol, new_recipient)?;
+        }
+        AdminAction::TransferAuthority { new_authority } => {
+            require!(
+                protocol.pending_authority.is_none(),
+                ShadowProtocolError::AuthorityTransferPending
+            );
+            let timelock_expires = clock.unix_timestamp + AUTHORITY_TRANSFER_TIMELOCK;
+            protocol.pending_authority = Some(new_authority);
+            protocol.authority_transfer_timelock = Some(timelock_expires);
+            emit!(AuthorityTransferInitiated {
+                current_authority: protocol.authority,
+                pending_authority: new_authority,
+                timelock_expires,
+            });
+            msg!("Authority transfer initiated. New authority: {}", new_authority);
+        }
+        AdminAction::Unpause => {
+            emit!(PauseFlagsUpdated {
+                previous_flags: protocol.pause_flags,
+                pause_flags: 0,
+                updated_by: ctx.accounts.executor.key(),
+            });
+            protocol.pause_flags = 0;
+            protocol.paused = false;
+            msg!("Protocol unpaused");
+        }
+        AdminAction::UnfreezeAuction { auction_id } => {
+            let auction = ctx.accounts.auction
+                .as_deref_mut()
+                .ok_or(ShadowProtocolError::InvalidAuctionAccount)?;
+            require!(
+                auction.auction_id == auction_id,
+                ShadowProtocolError::InvalidAuctionAccount
+            );
+            auction.frozen = false;
+            emit!(AuctionFreezeUpdated {
+                auction_id,
+                frozen: false,
+                updated_by: ctx.accounts.executor.key(),
+            });
+            msg!("Auction {} unfrozen", auction_id);
+        }
+        AdminAction::SetReferralFee { referral_fee_bps } => {
+            queue_referral_fee(protocol, referral_fee_bps)?;
+        }
+        AdminAction::SetFeeTiers { fee_tiers } => {
+            queue_fee_tiers(protocol, fee_tiers)?;
+        }
+        AdminAction::AddMember { member } => {
+            require!(
+                !multisig.members.contains(&member),
+                ShadowProtocolError::InvalidMultisigConfig
+            );
+            require!(
+                multisig.members.len() < MAX_MULTISIG_MEMBERS,
+                ShadowProtocolError::InvalidMultisigConfig
+            );
+            multisig.members.push(member);
+            msg!("Multisig member {} added", member);
+        }
+        AdminAction::RemoveMember { member } => {
+            let index = multisig.members
+                .iter()
+                .position(|m| *m == member)
+                .ok_or(ShadowProtocolError::NotMultisigMember)?;
+            // The remaining members must still be able to reach the threshold
+            require!(
+                multisig.members.len() > multisig.threshold as usize,
+                ShadowProtocolError::InvalidMultisigConfig
+            );
+            multisig.members.remove(index);
+            msg!("Multisig member {} removed", member);
+        }
+        AdminAction::ChangeThreshold { threshold } => {
+            require!(
+                threshold > 0 && threshold as usize <= multisig.members.len(),
+                ShadowProtocolError::InvalidMultisigConfig
+            );
+            multisig.threshold = threshold;
+            msg!("Multisig threshold set to {}", threshold);
+        }
+        AdminAction::SetFeeUpdateDelay { new_delay } => {
+            change_fee_update_delay(protocol, new_delay)?;
+        }
+        AdminAction::SetBidLimits { bid_deposit_lamports, max_active_bids_per_wallet } => {
+            change_bid_limits(protocol, bid_deposit_lamports, max_active_bids_per_wallet)?;
+        }
+        AdminAction::GrantRole { role, member } => {
+            let roles = ctx.accounts.roles
+                .as_deref_mut()
+                .ok_or(ShadowProtocolError::ProposalAccountMissing)?;
+            if let Some(bump) = ctx.bumps.roles {
+                roles.bump = bump;
+            }
+            add_role_grant(roles, role, member, ctx.accounts.executor.key())?;
+        }
+        AdminAction::RevokeRole { role, member } => {
+            let roles = ctx.accounts.roles
+                .as_deref_mut()
+                .ok_or(ShadowProtocolError::ProposalAccountMissing)?;
+            remove_role_grant(roles, role, member, ctx.accounts.executor.key())?;
+        }
+        #[cfg(feature = "mock-oracle")]
+        AdminAction::SetMockPrice { feed_id, price, conf, exponent } => {
+            let feed = ctx.accounts.mock_price_feed
+                .as_deref_mut()
+                .ok_or(ShadowProtocolError::ProposalAccountMissing)?;
+            require!(
+                feed.feed_id == feed_id,
+                ShadowProtocolError::InvalidPriceFeed
+            );
+            publish_mock_price(feed, feed_id, price, conf, exponent)?;
+        }
+    }
+    
+    if matches!(
+        proposal.action,
+        AdminAction::AddMember { .. } | AdminAction::RemoveMember { .. } | AdminAction::ChangeThreshold { .. }
+    ) {
+        emit!(MultisigConfigured {
+            multisig: multisig.key(),
+            member_count: multisig.members.len() as u8,
+            threshold: multisig.threshold,
+        });
+    }
+    
+    proposal.executed = true;
+    
+    emit!(ProposalExecuted {
+        proposal_id,
+        action: proposal.action.clone(),
+        executed_by: ctx.accounts.executor.key(),
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConfigureMultisig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AdminMultisig::INIT_SPACE,
+        seeds = [MULTISIG_SEED],
+        bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + AdminProposal::INIT_SPACE,
+        seeds = [PROPOSAL_SEED, multisig.next_proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ApproveProposal<'info> {
+    pub approver: Signer<'info>,
+    
+    #[account(
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [MULTISIG_SEED],
+        bump = multisig.bump
+    )]
+    pub multisig: Account<'info, AdminMultisig>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_SEED, proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, AdminProposal>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump,
+        constraint = protocol_state.admin_multisig == Some(multisig.key()) @ ShadowProtocolError::MultisigRequired
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Auction targeted by the proposal (UnfreezeAuction only)
+    #[account(mut)]
+    pub auction: Option<Account<'info, AuctionAccount>>,
+    
+    /// Role grants (GrantRole and RevokeRole only; created on first grant)
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = 8 + ProtocolRoles::INIT_SPACE,
+        seeds = [ROLES_SEED],
+        bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    /// Mock feed the proposal publishes to (SetMockPrice only)
+    #[cfg(feature = "mock-oracle")]
+    #[account(mut)]
+    pub mock_price_feed: Option<Account<'info, MockPriceFeed>>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
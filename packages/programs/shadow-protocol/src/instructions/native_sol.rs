@@ -0,0 +1,812 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+use super::auction_management::current_auction_time;
+use super::oracle::usd_to_payment_amount;
+use super::admin::require_not_paused;
+use super::bidding::{calculate_dutch_auction_price, open_bid_slot};
+use super::gating::require_bidder_allowed;
+use super::metadata::{read_royalty_metadata, transfer_programmable_nft, NftTransferAccounts};
+use super::settlement::{auction_settlement_splits, settlement_royalty_bps};
+
+/// System transfer of lamports out of a signer's wallet
+fn pay_lamports<'info>(
+    from: &Signer<'info>,
+    to: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: from.to_account_info(),
+                to,
+            },
+        ),
+        amount,
+    )
+}
+
+/// Moves the bidder's collateral into a freshly created lamport escrow
+fn fund_sol_escrow<'info>(
+    escrow: &mut Account<'info, SolEscrow>,
+    bidder: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    auction_id: u64,
+    amount: u64,
+    bump: u8,
+) -> Result<()> {
+    pay_lamports(bidder, escrow.to_account_info(), system_program, amount)?;
+    
+    escrow.auction_id = auction_id;
+    escrow.bidder = bidder.key();
+    escrow.amount = amount;
+    escrow.bump = bump;
+    
+    Ok(())
+}
+
+/// Submit an encrypted bid to a sealed auction with native SOL collateral
+#[allow(clippy::too_many_arguments)]
+pub fn submit_encrypted_bid_sol(
+    ctx: Context<SubmitSolBid>,
+    auction_id: u64,
+    bid_amount_encrypted: [u8; 32],
+    public_key: [u8; 32],
+    nonce: u128,
+    collateral_amount: u64,
+    referrer: Option<Pubkey>,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    
+    // Cap open bids per wallet and hold the spam deposit
+    open_bid_slot(
+        &accounts.protocol_state,
+        &mut accounts.bidder_stats,
+        ctx.bumps.bidder_stats,
+        &accounts.bidder,
+        &mut accounts.bid,
+        &accounts.system_program,
+    )?;
+    
+    // Non-refundable bond paid straight to the seller
+    let bond = accounts.auction.bid_bond;
+    if bond > 0 {
+        pay_lamports(
+            &accounts.bidder,
+            accounts.creator.to_account_info(),
+            &accounts.system_program,
+            bond,
+        )?;
+    }
+    
+    let auction = &mut accounts.auction;
+    let protocol = &accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    // Bidders cannot refer themselves to claw back part of the price
+    require!(
+        referrer != Some(accounts.bidder.key()),
+        ShadowProtocolError::InvalidReferrer
+    );
+    
+    require_not_paused(protocol, PAUSE_BID, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.auction_type == AuctionType::SealedBid,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require_bidder_allowed(
+        auction,
+        &accounts.bidder.key(),
+        &allowlist_proof,
+        accounts.gate_token_account.as_ref(),
+        accounts.gate_attester.as_ref(),
+    )?;
+    
+    require!(
+        auction.status == AuctionStatus::Active,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(
+        collateral_amount >= auction.minimum_bid,
+        ShadowProtocolError::InsufficientCollateral
+    );
+    
+    // USD minimum, converted at the oracle price when the bid is placed
+    if let Some(pricing) = &auction.usd_pricing {
+        let minimum_bid = usd_to_payment_amount(pricing, accounts.price_feed.as_ref(), pricing.minimum_bid_usd)?;
+        require!(
+            collateral_amount >= minimum_bid,
+            ShadowProtocolError::InsufficientCollateral
+        );
+    }
+    
+    // Validate collateral amount is reasonable (prevent overflow attacks)
+    require!(
+        collateral_amount <= u64::MAX / 2,
+        ShadowProtocolError::InvalidAssetAmount
+    );
+    
+    require!(
+        accounts.bidder.lamports() >= collateral_amount,
+        ShadowProtocolError::InsufficientFunds
+    );
+    
+    require!(
+        current_auction_time(auction.timing_mode, &clock) < auction.end_time,
+        ShadowProtocolError::AuctionEnded
+    );
+    
+    require!(
+        auction.bid_count < MAX_BIDS_PER_AUCTION as u64,
+        ShadowProtocolError::MaxBidsExceeded
+    );
+    
+    let bid = &mut accounts.bid;
+    bid.auction_id = auction_id;
+    bid.bidder = accounts.bidder.key();
+    bid.amount_encrypted = bid_amount_encrypted;
+    bid.encryption_public_key = public_key;
+    bid.nonce = nonce;
+    bid.timestamp = clock.unix_timestamp;
+    bid.collateral_amount = collateral_amount;
+    bid.collateral_account = accounts.sol_escrow.key();
+    bid.is_winner = false;
+    bid.quantity_encrypted = [0u8; 32];
+    bid.units_allocated = 0;
+    bid.settled = false;
+    bid.allocation_recorded = false;
+    bid.referrer = referrer;
+    bid.version = BID_ACCOUNT_VERSION;
+    bid.bump = ctx.bumps.bid;
+    
+    fund_sol_escrow(
+        &mut accounts.sol_escrow,
+        &accounts.bidder,
+        &accounts.system_program,
+        auction_id,
+        collateral_amount,
+        ctx.bumps.sol_escrow,
+    )?;
+    
+    auction.bid_count += 1;
+    
+    emit!(BidSubmitted {
+        auction_id,
+        bidder: accounts.bidder.key(),
+        timestamp: clock.unix_timestamp,
+        bid_count: auction.bid_count,
+    });
+    
+    Ok(())
+}
+
+/// Take a Dutch auction at the current price with native SOL collateral
+pub fn submit_dutch_bid_sol(
+    ctx: Context<SubmitDutchSolBid>,
+    auction_id: u64,
+    bid_amount: u64,
+    collateral_amount: u64,
+    allowlist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    require_not_paused(protocol, PAUSE_BID, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require_bidder_allowed(
+        auction,
+        &ctx.accounts.bidder.key(),
+        &allowlist_proof,
+        ctx.accounts.gate_token_account.as_ref(),
+        ctx.accounts.gate_attester.as_ref(),
+    )?;
+    
+    require!(
+        collateral_amount >= bid_amount,
+        ShadowProtocolError::InsufficientCollateral
+    );
+    
+    // Validate bid amount is reasonable (prevent overflow attacks)
+    require!(
+        bid_amount <= u64::MAX / 2,
+        ShadowProtocolError::InvalidAssetAmount
+    );
+    
+    require!(
+        ctx.accounts.bidder.lamports() >= collateral_amount,
+        ShadowProtocolError::InsufficientFunds
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Active,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(
+        current_auction_time(auction.timing_mode, &clock) < auction.end_time,
+        ShadowProtocolError::AuctionEnded
+    );
+    
+    let mut current_price = calculate_dutch_auction_price(auction)?;
+    
+    // USD floor, converted at the oracle price when the bid is placed
+    if let Some(pricing) = &auction.usd_pricing {
+        let floor = usd_to_payment_amount(
+            pricing,
+            ctx.accounts.price_feed.as_ref(),
+            pricing.minimum_price_floor_usd,
+        )?;
+        current_price = current_price.max(floor);
+    }
+    
+    require!(
+        bid_amount >= current_price,
+        ShadowProtocolError::DutchPriceNotMet
+    );
+    
+    require!(
+        current_price >= auction.minimum_price_floor,
+        ShadowProtocolError::PriceBelowMinimumFloor
+    );
+    
+    fund_sol_escrow(
+        &mut ctx.accounts.sol_escrow,
+        &ctx.accounts.bidder,
+        &ctx.accounts.system_program,
+        auction_id,
+        collateral_amount,
+        ctx.bumps.sol_escrow,
+    )?;
+    
+    // Dutch auction ends immediately when bid meets price
+    auction.status = AuctionStatus::Ended;
+    auction.winner = Some(ctx.accounts.bidder.key());
+    auction.winning_amount = current_price;
+    auction.bid_count = 1;
+    
+    emit!(BidSubmitted {
+        auction_id,
+        bidder: ctx.accounts.bidder.key(),
+        timestamp: clock.unix_timestamp,
+        bid_count: 1,
+    });
+    
+    Ok(())
+}
+
+/// Settle an auction paid in native SOL. The winner pays the seller,
+/// referrer, royalty creators and protocol in lamports; the asset moves
+/// exactly as in `execute_settlement`. Remaining accounts are the creator
+/// wallets from the asset's metadata, followed by the programmable NFT
+/// transfer accounts when the asset is a pNFT.
+pub fn execute_sol_settlement<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSolSettlement<'info>>,
+    auction_id: u64,
+    winner: Pubkey,
+    winning_amount: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    
+    require_not_paused(protocol, PAUSE_SETTLE, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.settlement_authorized,
+        ShadowProtocolError::SettlementNotAuthorized
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(
+        winning_amount > 0,
+        ShadowProtocolError::InvalidAssetAmount
+    );
+    
+    // Public USD reserve, converted at the oracle price at settlement
+    if let Some(pricing) = &auction.usd_pricing {
+        let reserve = usd_to_payment_amount(pricing, ctx.accounts.price_feed.as_ref(), pricing.reserve_usd)?;
+        require!(winning_amount >= reserve, ShadowProtocolError::ReserveNotMet);
+    }
+    
+    require!(
+        ctx.accounts.asset_vault.amount >= auction.asset_amount,
+        ShadowProtocolError::InvalidAssetAmount
+    );
+    
+    require!(
+        winner != Pubkey::default() && winner == ctx.accounts.winner.key(),
+        ShadowProtocolError::InvalidWinnerDetermination
+    );
+    
+    auction.winner = Some(winner);
+    auction.winning_amount = winning_amount;
+    auction.status = AuctionStatus::Settled;
+    auction.settled_at = Some(Clock::get()?.unix_timestamp);
+    
+    // Referral fee only applies when the winning bid names a referrer.
+    // Sealed-bid wins must pass the bid; Dutch wins have none.
+    let referrer = match &ctx.accounts.winning_bid {
+        Some(bid) => bid.referrer,
+        None => {
+            require!(
+                auction.auction_type == AuctionType::Dutch,
+                ShadowProtocolError::SettlementAccountMismatch
+            );
+            None
+        }
+    };
+    
+    let metadata = read_royalty_metadata(&ctx.accounts.asset_metadata, &auction.asset_mint)?;
+    
+    // NFT listings always pay creator royalties
+    require!(
+        auction.nft.is_none() || metadata.is_some(),
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    let royalty_bps = settlement_royalty_bps(auction, metadata.as_ref());
+    
+    let seller_stats = &mut ctx.accounts.seller_stats;
+    let mut splits = auction_settlement_splits(
+        auction,
+        protocol,
+        seller_stats,
+        referrer,
+        royalty_bps,
+        winning_amount,
+    )?;
+    
+    let auction_id_bytes = auction.auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let signer_seeds = &[&auction_seeds[..]];
+    
+    let creator_count = metadata.as_ref().map_or(0, |metadata| metadata.creators.len());
+    require!(
+        ctx.remaining_accounts.len() >= creator_count,
+        ShadowProtocolError::SettlementAccountMismatch
+    );
+    let (royalty_accounts, nft_transfer_accounts) = ctx.remaining_accounts.split_at(creator_count);
+    
+    // Transfer asset to winner
+    if auction.nft.is_some_and(|listing| listing.programmable) {
+        transfer_programmable_nft(
+            NftTransferAccounts {
+                token: ctx.accounts.asset_vault.to_account_info(),
+                token_owner: auction.to_account_info(),
+                destination: ctx.accounts.winner_asset_account.to_account_info(),
+                destination_owner: ctx.accounts.winner.to_account_info(),
+                mint: ctx.accounts.asset_mint.to_account_info(),
+                metadata: ctx.accounts.asset_metadata.to_account_info(),
+                authority: auction.to_account_info(),
+                payer: ctx.accounts.winner.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                extra: nft_transfer_accounts,
+            },
+            auction.asset_amount,
+            signer_seeds,
+        )?;
+    } else {
+        require!(
+            nft_transfer_accounts.is_empty(),
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.asset_vault.to_account_info(),
+                    to: ctx.accounts.winner_asset_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            auction.asset_amount,
+        )?;
+    }
+    
+    // Pay creator royalties by share; rounding dust stays with the seller
+    if let Some(metadata) = &metadata {
+        let mut royalties_paid: u64 = 0;
+        for (creator, creator_info) in metadata.creators.iter().zip(royalty_accounts.iter()) {
+            require!(
+                creator_info.key() == creator.address && creator_info.is_writable,
+                ShadowProtocolError::SettlementAccountMismatch
+            );
+            
+            let share_amount = splits.royalty_fee
+                .checked_mul(creator.share as u64)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?
+                / 100;
+            
+            if share_amount > 0 {
+                pay_lamports(
+                    &ctx.accounts.winner,
+                    creator_info.clone(),
+                    &ctx.accounts.system_program,
+                    share_amount,
+                )?;
+            }
+            
+            royalties_paid = royalties_paid
+                .checked_add(share_amount)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+        }
+        
+        require!(
+            royalties_paid <= splits.royalty_fee,
+            ShadowProtocolError::SettlementSplitsExceedPrice
+        );
+        
+        splits.seller_amount += splits.royalty_fee - royalties_paid;
+        splits.royalty_fee = royalties_paid;
+    }
+    
+    // Pay the referrer
+    if splits.referral_fee > 0 {
+        let referrer_wallet = ctx.accounts.referrer
+            .as_ref()
+            .ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        require!(
+            Some(referrer_wallet.key()) == referrer,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        pay_lamports(
+            &ctx.accounts.winner,
+            referrer_wallet.to_account_info(),
+            &ctx.accounts.system_program,
+            splits.referral_fee,
+        )?;
+    }
+    
+    // Pay the seller (minus fees)
+    pay_lamports(
+        &ctx.accounts.winner,
+        ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.system_program,
+        splits.seller_amount,
+    )?;
+    
+    // Protocol fee accrues as lamports on the SOL fee vault
+    let fee_vault = &mut ctx.accounts.sol_fee_vault;
+    fee_vault.mint = NATIVE_SOL_MINT;
+    fee_vault.bump = ctx.bumps.sol_fee_vault;
+    if splits.protocol_fee > 0 {
+        pay_lamports(
+            &ctx.accounts.winner,
+            fee_vault.to_account_info(),
+            &ctx.accounts.system_program,
+            splits.protocol_fee,
+        )?;
+        
+        emit!(ProtocolFeeCollected {
+            auction_id,
+            mint: NATIVE_SOL_MINT,
+            amount: splits.protocol_fee,
+        });
+    }
+    
+    // Track seller volume for fee tiers
+    seller_stats.seller = auction.creator;
+    seller_stats.total_volume = seller_stats.total_volume.saturating_add(winning_amount);
+    seller_stats.settled_count = seller_stats.settled_count.saturating_add(1);
+    seller_stats.bump = ctx.bumps.seller_stats;
+    
+    emit!(AuctionSettled {
+        auction_id,
+        winner: Some(winner),
+        winning_amount,
+        splits: Some(splits),
+        settled_at: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Return native SOL collateral once an auction is settled or cancelled.
+/// The escrow is closed, so its rent comes back with the collateral.
+pub fn claim_sol_refund(ctx: Context<ClaimSolRefund>, auction_id: u64) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let amount = ctx.accounts.sol_escrow.amount;
+    
+    require!(
+        auction.status == AuctionStatus::Settled || auction.status == AuctionStatus::Cancelled,
+        ShadowProtocolError::RefundNotAvailable
+    );
+    
+    match ctx.accounts.bid.as_mut() {
+        Some(bid) => {
+            require!(
+                bid.collateral_account == ctx.accounts.sol_escrow.key(),
+                ShadowProtocolError::InvalidEscrowAccount
+            );
+            require!(!bid.settled, ShadowProtocolError::RefundNotAvailable);
+            
+            bid.settled = true;
+            
+            // Release the anti-spam deposit held on the bid account
+            let deposit = bid.deposit_lamports;
+            if deposit > 0 {
+                bid.deposit_lamports = 0;
+                bid.sub_lamports(deposit)?;
+                ctx.accounts.bidder.add_lamports(deposit)?;
+            }
+        }
+        // Only Dutch bids escrow collateral without a bid account
+        None => require!(
+            auction.auction_type == AuctionType::Dutch,
+            ShadowProtocolError::InvalidEscrowAccount
+        ),
+    }
+    
+    emit!(BidRefunded {
+        auction_id,
+        bidder: ctx.accounts.bidder.key(),
+        amount,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct SubmitSolBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        constraint = auction.payment_mint == NATIVE_SOL_MINT @ ShadowProtocolError::InvalidPaymentMint
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + BidAccount::INIT_SPACE,
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, BidAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Lamport escrow holding the collateral
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + SolEscrow::INIT_SPACE,
+        seeds = [SOL_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    /// Bidder's open bid count across auctions
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = 8 + BidderStats::INIT_SPACE,
+        seeds = [BIDDER_STATS_SEED, bidder.key().as_ref()],
+        bump
+    )]
+    pub bidder_stats: Account<'info, BidderStats>,
+    
+    /// Seller's wallet receiving the bid bond
+    /// CHECK: Address is constrained to the auction creator
+    #[account(
+        mut,
+        address = auction.creator
+    )]
+    pub creator: UncheckedAccount<'info>,
+    
+    /// Oracle price account (required when the auction has USD pricing)
+    /// CHECK: Matched against the auction's price feed and parsed in the handler
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// Bidder's token account for token-holding gates
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Attester co-signing bids on attester-gated auctions
+    pub gate_attester: Option<Signer<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct SubmitDutchSolBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        constraint = auction.auction_type == AuctionType::Dutch @ ShadowProtocolError::InvalidAuctionType,
+        constraint = auction.payment_mint == NATIVE_SOL_MINT @ ShadowProtocolError::InvalidPaymentMint
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Lamport escrow holding the collateral
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + SolEscrow::INIT_SPACE,
+        seeds = [SOL_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+    
+    /// Oracle price account (required when the auction has USD pricing)
+    /// CHECK: Matched against the auction's price feed and parsed in the handler
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// Bidder's token account for token-holding gates
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Attester co-signing bids on attester-gated auctions
+    pub gate_attester: Option<Signer<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct ExecuteSolSettlement<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        constraint = auction.payment_mint == NATIVE_SOL_MINT @ ShadowProtocolError::InvalidPaymentMint
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Asset vault holding the auctioned item
+    #[account(
+        mut,
+        seeds = [ASSET_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+    
+    /// Winner's asset account
+    #[account(
+        mut,
+        associated_token::mint = auction.asset_mint,
+        associated_token::authority = winner
+    )]
+    pub winner_asset_account: Account<'info, TokenAccount>,
+    
+    /// Asset mint
+    #[account(address = auction.asset_mint)]
+    pub asset_mint: Account<'info, Mint>,
+    
+    /// Seller's wallet receiving the proceeds
+    /// CHECK: Address is constrained to the auction creator
+    #[account(
+        mut,
+        address = auction.creator
+    )]
+    pub creator: UncheckedAccount<'info>,
+    
+    /// Protocol fees collected in native SOL
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [SOL_FEE_VAULT_SEED],
+        bump
+    )]
+    pub sol_fee_vault: Account<'info, FeeLedger>,
+    
+    /// Winning sealed bid, which carries the referrer (required unless the
+    /// auction is Dutch, whose wins have no bid account)
+    #[account(
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump = winning_bid.bump
+    )]
+    pub winning_bid: Option<Account<'info, BidAccount>>,
+    
+    /// Referrer's wallet (required when a referral fee is owed)
+    /// CHECK: Matched against the winning bid's referrer in the handler
+    #[account(mut)]
+    pub referrer: Option<UncheckedAccount<'info>>,
+    
+    /// Oracle price account (required when the auction has USD pricing)
+    /// CHECK: Matched against the auction's price feed and parsed in the handler
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// Metaplex metadata address for the asset mint, which pays creator
+    /// royalties when initialized
+    /// CHECK: Owner, address and layout are validated in the handler
+    #[account(mut)]
+    pub asset_metadata: UncheckedAccount<'info>,
+    
+    /// Seller's settled SOL volume, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + SellerStats::INIT_SPACE,
+        seeds = [SELLER_STATS_SEED, auction.creator.as_ref(), NATIVE_SOL_MINT.as_ref()],
+        bump
+    )]
+    pub seller_stats: Account<'info, SellerStats>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct ClaimSolRefund<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    
+    #[account(
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    /// Sealed bid the collateral backs (omitted for Dutch bids)
+    #[account(
+        mut,
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump,
+        has_one = bidder
+    )]
+    pub bid: Option<Account<'info, BidAccount>>,
+    
+    /// Lamport escrow, closed back to the bidder
+    #[account(
+        mut,
+        seeds = [SOL_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bidder.key().as_ref()],
+        bump = sol_escrow.bump,
+        has_one = bidder,
+        close = bidder
+    )]
+    pub sol_escrow: Account<'info, SolEscrow>,
+}
\ No newline at end of file
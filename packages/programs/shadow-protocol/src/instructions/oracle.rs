@@ -0,0 +1,275 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+#[cfg(feature = "mock-oracle")]
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+
+// PriceUpdateV2 verification level tag for fully verified updates
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+
+/// Price read from an oracle account
+struct OraclePrice {
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    publish_time: i64,
+}
+
+/// Reads a Pyth `PriceUpdateV2` account, or a protocol mock feed in
+/// `mock-oracle` builds. Only the fields needed for conversion are decoded,
+/// so no Pyth crate dependency is needed.
+fn read_oracle_price(feed: &AccountInfo, feed_id: &[u8; 32]) -> Result<OraclePrice> {
+    let data = feed.try_borrow_data()?;
+    
+    let (price_feed_id, price) = if feed.owner == &PYTH_RECEIVER_PROGRAM_ID {
+        let discriminator = hash(b"account:PriceUpdateV2").to_bytes();
+        require!(
+            data.get(..8) == Some(&discriminator[..8]),
+            ShadowProtocolError::InvalidPriceFeed
+        );
+        
+        // Discriminator, then the write authority
+        let mut offset = 8 + 32;
+        require!(
+            data.get(offset) == Some(&VERIFICATION_LEVEL_FULL),
+            ShadowProtocolError::InvalidPriceFeed
+        );
+        offset += 1;
+        
+        // feed_id, price, conf, exponent, publish_time
+        let message = data
+            .get(offset..offset + 32 + 8 + 8 + 4 + 8)
+            .ok_or(ShadowProtocolError::InvalidPriceFeed)?;
+        let mut price_feed_id = [0u8; 32];
+        price_feed_id.copy_from_slice(&message[..32]);
+        (
+            price_feed_id,
+            OraclePrice {
+                price: i64::from_le_bytes(message[32..40].try_into().unwrap()),
+                conf: u64::from_le_bytes(message[40..48].try_into().unwrap()),
+                exponent: i32::from_le_bytes(message[48..52].try_into().unwrap()),
+                publish_time: i64::from_le_bytes(message[52..60].try_into().unwrap()),
+            },
+        )
+    } else {
+        read_mock_feed(feed, &data)?
+    };
+    
+    require!(
+        price_feed_id == *feed_id,
+        ShadowProtocolError::InvalidPriceFeed
+    );
+    
+    Ok(price)
+}
+
+/// Decodes a protocol-owned mock feed
+#[cfg(feature = "mock-oracle")]
+fn read_mock_feed(feed: &AccountInfo, data: &[u8]) -> Result<([u8; 32], OraclePrice)> {
+    require!(
+        feed.owner == &crate::ID && data.starts_with(MockPriceFeed::DISCRIMINATOR),
+        ShadowProtocolError::InvalidPriceFeed
+    );
+    
+    let mock = MockPriceFeed::try_deserialize(&mut &data[..])?;
+    Ok((
+        mock.feed_id,
+        OraclePrice {
+            price: mock.price,
+            conf: mock.conf,
+            exponent: mock.exponent,
+            publish_time: mock.publish_time,
+        },
+    ))
+}
+
+/// Only Pyth feeds are accepted outside `mock-oracle` builds
+#[cfg(not(feature = "mock-oracle"))]
+fn read_mock_feed(_feed: &AccountInfo, _data: &[u8]) -> Result<([u8; 32], OraclePrice)> {
+    err!(ShadowProtocolError::InvalidPriceFeed)
+}
+
+/// Converts a micro-USD amount into payment token base units at the current
+/// oracle price. The lower edge of the confidence interval is used, so the
+/// seller always receives at least the USD amount. Returns 0 for a zero
+/// USD amount without reading the oracle.
+pub(crate) fn usd_to_payment_amount(
+    pricing: &UsdPricing,
+    price_feed: Option<&UncheckedAccount>,
+    usd_amount: u64,
+) -> Result<u64> {
+    if usd_amount == 0 {
+        return Ok(0);
+    }
+    
+    let price_feed = price_feed.ok_or(ShadowProtocolError::InvalidPriceFeed)?;
+    require!(
+        price_feed.key() == pricing.price_feed,
+        ShadowProtocolError::InvalidPriceFeed
+    );
+    
+    let oracle = read_oracle_price(price_feed, &pricing.feed_id)?;
+    
+    let age = Clock::get()?.unix_timestamp.saturating_sub(oracle.publish_time);
+    require!(age <= MAX_PRICE_AGE, ShadowProtocolError::StalePrice);
+    
+    require!(oracle.price > 0, ShadowProtocolError::InvalidPriceFeed);
+    let price = oracle.price as u128;
+    let conf = oracle.conf as u128;
+    require!(
+        conf * 10_000 <= price * MAX_PRICE_CONFIDENCE_BPS as u128,
+        ShadowProtocolError::PriceConfidenceTooWide
+    );
+    let price = price - conf;
+    
+    let pow10 = |exp: u32| {
+        10u128
+            .checked_pow(exp)
+            .ok_or(ShadowProtocolError::FeeCalculationOverflow)
+    };
+    
+    // amount = usd * 10^decimals / (price * 10^exponent * 10^USD_DECIMALS)
+    let mut numerator = (usd_amount as u128)
+        .checked_mul(pow10(pricing.payment_decimals as u32)?)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    let mut denominator = price
+        .checked_mul(pow10(USD_DECIMALS)?)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    if oracle.exponent < 0 {
+        numerator = numerator
+            .checked_mul(pow10(oracle.exponent.unsigned_abs())?)
+            .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    } else {
+        denominator = denominator
+            .checked_mul(pow10(oracle.exponent as u32)?)
+            .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    }
+    
+    let amount = numerator.div_ceil(denominator);
+    u64::try_from(amount).map_err(|_| error!(ShadowProtocolError::FeeCalculationOverflow))
+}
+
+/// Denominate an auction's minimum bid, Dutch floor and public reserve in
+/// USD. Can only change before the first bid.
+pub fn set_usd_pricing(
+    ctx: Context<UpdateUsdPricing>,
+    auction_id: u64,
+    pricing: Option<UsdPricing>,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    
+    require!(
+        auction.auction_type == AuctionType::SealedBid || auction.auction_type == AuctionType::Dutch,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Active && auction.bid_count == 0,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    if let Some(pricing) = &pricing {
+        require!(
+            pricing.price_feed != Pubkey::default() && pricing.payment_decimals <= 18,
+            ShadowProtocolError::InvalidUsdPricing
+        );
+    }
+    
+    auction.usd_pricing = pricing;
+    
+    emit!(UsdPricingUpdated {
+        auction_id,
+        pricing,
+    });
+    
+    Ok(())
+}
+
+/// Write a price onto a mock feed
+#[cfg(feature = "mock-oracle")]
+pub(crate) fn publish_mock_price(
+    feed: &mut MockPriceFeed,
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+) -> Result<()> {
+    feed.feed_id = feed_id;
+    feed.price = price;
+    feed.conf = conf;
+    feed.exponent = exponent;
+    feed.publish_time = Clock::get()?.unix_timestamp;
+    
+    msg!("Mock price {} x 10^{} published", price, exponent);
+    
+    Ok(())
+}
+
+/// Publish a price on a protocol-owned mock feed (protocol authority only).
+/// Only built with the `mock-oracle` feature for localnet testing; auctions
+/// only read a mock feed when the seller names it as their price feed.
+#[cfg(feature = "mock-oracle")]
+pub fn set_mock_price(
+    ctx: Context<SetMockPrice>,
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
+    
+    require!(
+        ctx.accounts.authority.key() == protocol.authority,
+        ShadowProtocolError::Unauthorized
+    );
+    
+    // Sensitive changes go through multisig proposals once one is configured
+    require!(
+        protocol.admin_multisig.is_none(),
+        ShadowProtocolError::MultisigRequired
+    );
+    
+    let feed = &mut ctx.accounts.mock_price_feed;
+    feed.bump = ctx.bumps.mock_price_feed;
+    publish_mock_price(feed, feed_id, price, conf, exponent)
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct UpdateUsdPricing<'info> {
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+}
+
+#[cfg(feature = "mock-oracle")]
+#[derive(Accounts)]
+#[instruction(feed_id: [u8; 32])]
+pub struct SetMockPrice<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MockPriceFeed::INIT_SPACE,
+        seeds = [MOCK_PRICE_FEED_SEED, feed_id.as_ref()],
+        bump
+    )]
+    pub mock_price_feed: Account<'info, MockPriceFeed>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
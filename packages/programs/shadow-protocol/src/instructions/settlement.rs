@@ -8,6 +8,236 @@ use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
 // };
 use crate::state::*;
 use crate::error::ShadowProtocolError;
+use super::auction_management::current_auction_time;
+use super::oracle::usd_to_payment_amount;
+use super::keeper::pay_keeper_bounty;
+use super::admin::{require_role, require_not_paused};
+use super::metadata::{read_royalty_metadata, transfer_programmable_nft, AssetMetadata, NftTransferAccounts};
+
+/// Calculate the protocol fee owed on a payment amount
+pub(crate) fn calculate_protocol_fee(amount: u64, fee_bps: u16) -> Result<u64> {
+    let fee_amount = amount
+        .checked_mul(fee_bps as u64)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?
+        .checked_div(10000)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    Ok(fee_amount)
+}
+
+/// Protocol fee after applying the highest volume tier the seller reaches.
+/// Tiers only ever discount the fee snapshotted on the auction.
+pub(crate) fn tiered_protocol_fee_bps(auction_fee_bps: u16, fee_tiers: &[FeeTier], seller_volume: u64) -> u16 {
+    fee_tiers
+        .iter()
+        .rev()
+        .find(|tier| seller_volume >= tier.min_volume)
+        .map_or(auction_fee_bps, |tier| tier.fee_bps.min(auction_fee_bps))
+}
+
+/// Split a sale price between the protocol, the referrer, the asset's
+/// creators and the seller. Fails if the fees together exceed the price.
+pub(crate) fn calculate_settlement_splits(
+    price: u64,
+    protocol_fee_bps: u16,
+    referral_fee_bps: u16,
+    royalty_bps: u16,
+) -> Result<SettlementSplits> {
+    let protocol_fee = calculate_protocol_fee(price, protocol_fee_bps)?;
+    let referral_fee = calculate_protocol_fee(price, referral_fee_bps)?;
+    let royalty_fee = calculate_protocol_fee(price, royalty_bps)?;
+    
+    let total_fees = protocol_fee
+        .checked_add(referral_fee)
+        .and_then(|total| total.checked_add(royalty_fee))
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    let seller_amount = price
+        .checked_sub(total_fees)
+        .ok_or(ShadowProtocolError::SettlementSplitsExceedPrice)?;
+    
+    Ok(SettlementSplits {
+        protocol_fee,
+        referral_fee,
+        royalty_fee,
+        seller_amount,
+    })
+}
+
+/// Royalty rate a sale pays: the rate snapshotted when an NFT was listed,
+/// otherwise the asset metadata's current rate
+pub(crate) fn settlement_royalty_bps(auction: &AuctionAccount, metadata: Option<&AssetMetadata>) -> u16 {
+    auction.nft_royalty_bps
+        .or_else(|| metadata.map(|metadata| metadata.seller_fee_basis_points))
+        .unwrap_or(0)
+}
+
+/// Splits of an auction sale: the protocol fee discounted by the seller's
+/// volume tier, the referral fee when the winning bid names a referrer, and
+/// the creator royalty.
+pub(crate) fn auction_settlement_splits(
+    auction: &AuctionAccount,
+    protocol: &ProtocolState,
+    seller_stats: &SellerStats,
+    referrer: Option<Pubkey>,
+    royalty_bps: u16,
+    price: u64,
+) -> Result<SettlementSplits> {
+    let referral_fee_bps = if referrer.is_some() {
+        auction.referral_fee_bps
+    } else {
+        0
+    };
+    let protocol_fee_bps = tiered_protocol_fee_bps(
+        auction.protocol_fee_bps,
+        &protocol.fee_tiers,
+        seller_stats.total_volume,
+    );
+    
+    // Royalties only get what the fees leave of the price, so a raised rate
+    // cannot leave an authorized auction unable to settle
+    let royalty_bps = royalty_bps.min(
+        10000u16
+            .saturating_sub(protocol_fee_bps)
+            .saturating_sub(referral_fee_bps),
+    );
+    
+    calculate_settlement_splits(price, protocol_fee_bps, referral_fee_bps, royalty_bps)
+}
+
+/// Token accounts a sale price is paid out through
+pub(crate) struct SettlementPayment<'a, 'info> {
+    /// Account the price is paid from: the winner's wallet or the winning
+    /// bid's escrow
+    pub source: AccountInfo<'info>,
+    /// Authority over `source`
+    pub authority: AccountInfo<'info>,
+    pub seller: AccountInfo<'info>,
+    pub protocol_fee_vault: AccountInfo<'info>,
+    /// Referrer's payment account, required when a referral fee is owed
+    pub referrer: Option<AccountInfo<'info>>,
+    /// One payment account per metadata creator, in metadata order
+    pub royalty_accounts: &'a [AccountInfo<'info>],
+    pub payment_mint: Pubkey,
+    pub token_program: AccountInfo<'info>,
+}
+
+/// Read a token account owned by the token program
+fn read_token_account(info: &AccountInfo) -> Result<TokenAccount> {
+    require!(
+        info.owner == &anchor_spl::token::ID,
+        ShadowProtocolError::SettlementAccountMismatch
+    );
+    let data = info.try_borrow_data()?;
+    TokenAccount::try_deserialize(&mut &data[..])
+}
+
+/// Pay a sale's splits out of `payment.source`: creator royalties by share,
+/// the referral fee, the seller's proceeds and the protocol fee. Royalty
+/// rounding dust stays with the seller. Returns the splits as paid.
+pub(crate) fn pay_settlement_splits<'info>(
+    payment: SettlementPayment<'_, 'info>,
+    mut splits: SettlementSplits,
+    metadata: Option<&AssetMetadata>,
+    referrer: Option<Pubkey>,
+    auction_id: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<SettlementSplits> {
+    let pay = |to: &AccountInfo<'info>, amount: u64| -> Result<()> {
+        transfer(
+            CpiContext::new_with_signer(
+                payment.token_program.clone(),
+                Transfer {
+                    from: payment.source.clone(),
+                    to: to.clone(),
+                    authority: payment.authority.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    };
+    
+    // Pay creator royalties by share; rounding dust stays with the seller
+    if let Some(metadata) = metadata {
+        require!(
+            payment.royalty_accounts.len() == metadata.creators.len(),
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        let mut royalties_paid: u64 = 0;
+        for (creator, creator_info) in metadata.creators.iter().zip(payment.royalty_accounts.iter()) {
+            let creator_account = read_token_account(creator_info)?;
+            require!(
+                creator_account.mint == payment.payment_mint
+                    && creator_account.owner == creator.address,
+                ShadowProtocolError::SettlementAccountMismatch
+            );
+            
+            let share_amount = splits.royalty_fee
+                .checked_mul(creator.share as u64)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?
+                / 100;
+            
+            if share_amount > 0 {
+                pay(creator_info, share_amount)?;
+            }
+            
+            royalties_paid = royalties_paid
+                .checked_add(share_amount)
+                .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+        }
+        
+        require!(
+            royalties_paid <= splits.royalty_fee,
+            ShadowProtocolError::SettlementSplitsExceedPrice
+        );
+        
+        splits.seller_amount += splits.royalty_fee - royalties_paid;
+        splits.royalty_fee = royalties_paid;
+    }
+    
+    // Pay the referrer
+    if splits.referral_fee > 0 {
+        let referrer_info = payment.referrer
+            .as_ref()
+            .ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let referrer_account = read_token_account(referrer_info)?;
+        require!(
+            Some(referrer_account.owner) == referrer
+                && referrer_account.mint == payment.payment_mint,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        pay(referrer_info, splits.referral_fee)?;
+    }
+    
+    // Pay the seller (minus fees)
+    if splits.seller_amount > 0 {
+        pay(&payment.seller, splits.seller_amount)?;
+    }
+    
+    // Transfer fee to protocol
+    if splits.protocol_fee > 0 {
+        pay(&payment.protocol_fee_vault, splits.protocol_fee)?;
+        
+        emit!(ProtocolFeeCollected {
+            auction_id,
+            mint: payment.payment_mint,
+            amount: splits.protocol_fee,
+        });
+    }
+    
+    Ok(splits)
+}
+
+/// Track a seller's settled volume for fee tiers
+pub(crate) fn record_seller_sale(seller_stats: &mut SellerStats, seller: Pubkey, amount: u64, bump: u8) {
+    seller_stats.seller = seller;
+    seller_stats.total_volume = seller_stats.total_volume.saturating_add(amount);
+    seller_stats.settled_count = seller_stats.settled_count.saturating_add(1);
+    seller_stats.bump = bump;
+}
 
 /// Authorize settlement after MPC computation verification
 pub fn authorize_settlement(
@@ -24,11 +254,13 @@ pub fn authorize_settlement(
         ShadowProtocolError::InvalidAuctionStatus
     );
     
-    // Only protocol authority can authorize settlement
-    require!(
-        ctx.accounts.authority.key() == protocol.authority,
-        ShadowProtocolError::Unauthorized
-    );
+    // Only the protocol authority or a settlement attester can authorize settlement
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::SettlementAttester,
+        ctx.accounts.authority.key(),
+    )?;
 
     require!(
         auction.status == AuctionStatus::Ended,
@@ -40,6 +272,17 @@ pub fn authorize_settlement(
         ShadowProtocolError::AuctionAlreadySettled
     );
 
+    // Batched auctions are settled through `apply_batch_settlement`
+    require!(auction.batch.is_none(), ShadowProtocolError::AuctionAlreadyBatched);
+
+    // Reverse auctions record their result through `authorize_reverse_settlement`
+    require!(
+        auction.auction_type != AuctionType::Reverse,
+        ShadowProtocolError::InvalidAuctionType
+    );
+
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+
     // Store MPC verification hash and authorize settlement
     auction.mpc_verification_hash = Some(mpc_verification_hash);
     auction.settlement_authorized = true;
@@ -51,8 +294,8 @@ pub fn authorize_settlement(
 
 pub fn settle_auction(
     ctx: Context<SettleAuction>,
-    auction_id: u64,
-    computation_offset: u64,
+    _auction_id: u64,
+    _computation_offset: u64,
 ) -> Result<()> {
     let auction = &mut ctx.accounts.auction;
     let protocol = &ctx.accounts.protocol_state;
@@ -64,7 +307,7 @@ pub fn settle_auction(
     );
     
     require!(
-        clock.unix_timestamp >= auction.end_time,
+        current_auction_time(auction.timing_mode, &clock) >= auction.end_time,
         ShadowProtocolError::AuctionNotEnded
     );
     
@@ -73,11 +316,16 @@ pub fn settle_auction(
         ShadowProtocolError::AuctionAlreadySettled
     );
     
-    require!(!protocol.paused, ShadowProtocolError::ProtocolPaused);
+    require_not_paused(protocol, PAUSE_SETTLE, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
     
     // Mark as ended to trigger MPC computation
     auction.status = AuctionStatus::Ended;
     
+    // Reward the keeper that cranked the auction closed
+    pay_keeper_bounty(auction, &ctx.accounts.settler.to_account_info())?;
+    
     // TODO: Re-enable MPC computation after fixing Arcium imports
     // Prepare arguments for MPC computation
     // let mut args = Vec::new();
@@ -104,25 +352,91 @@ pub fn settle_auction(
     Ok(())
 }
 
-pub fn batch_settle(
-    ctx: Context<BatchSettle>,
+/// Commit a set of ended sealed-bid auctions to a single batch settlement.
+/// The auction accounts are passed as remaining accounts in the same order
+/// as `auction_ids` and must all belong to the batch creator.
+pub fn batch_settle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchSettle<'info>>,
     auction_ids: Vec<u64>,
-    computation_offset: u64,
+    _computation_offset: u64,
 ) -> Result<()> {
     let batch = &mut ctx.accounts.batch;
+    let protocol = &mut ctx.accounts.protocol_state;
     let clock = Clock::get()?;
     
     require!(
-        auction_ids.len() > 0 && auction_ids.len() <= 10,
+        !auction_ids.is_empty() && auction_ids.len() <= 10,
+        ShadowProtocolError::InvalidBatchSize
+    );
+    
+    require!(
+        auction_ids.len() == ctx.remaining_accounts.len(),
         ShadowProtocolError::InvalidBatchSize
     );
     
-    batch.batch_id = clock.unix_timestamp as u64;
-    batch.creator = ctx.accounts.creator.key();
+    let batch_key = batch.key();
+    let creator = ctx.accounts.creator.key();
+    
+    for (i, (auction_info, auction_id)) in ctx.remaining_accounts.iter().zip(auction_ids.iter()).enumerate() {
+        require!(
+            !auction_ids[..i].contains(auction_id),
+            ShadowProtocolError::InvalidBatchSize
+        );
+        
+        let mut auction: Account<'info, AuctionAccount> = Account::try_from(auction_info)?;
+        
+        require!(
+            auction.auction_id == *auction_id,
+            ShadowProtocolError::AuctionNotInBatch
+        );
+        
+        require!(
+            auction.auction_type == AuctionType::SealedBid,
+            ShadowProtocolError::InvalidAuctionType
+        );
+        
+        require!(
+            auction.status == AuctionStatus::Ended,
+            ShadowProtocolError::InvalidAuctionStatus
+        );
+        
+        require!(
+            !auction.settlement_authorized,
+            ShadowProtocolError::AuctionAlreadySettled
+        );
+        
+        require!(auction.batch.is_none(), ShadowProtocolError::AuctionAlreadyBatched);
+        
+        // NFT transfers and USD reserves need accounts a batch does not carry
+        require!(
+            auction.nft.is_none() && auction.usd_pricing.is_none(),
+            ShadowProtocolError::AuctionNotBatchable
+        );
+        
+        require!(
+            auction.creator == creator,
+            ShadowProtocolError::BatchCreatorMismatch
+        );
+        
+        // The keeper bounty was paid when settle_auction ended the auction;
+        // the batch creator is the seller and never collects it
+        auction.batch = Some(batch_key);
+        auction.exit(&crate::ID)?;
+    }
+    
+    batch.batch_id = protocol.next_batch_id;
+    
+    protocol.next_batch_id = protocol.next_batch_id
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    batch.creator = creator;
     batch.auction_ids = auction_ids.clone();
     batch.status = BatchStatus::Created;
     batch.created_at = clock.unix_timestamp;
     batch.settled_at = None;
+    batch.failed_auction_ids = Vec::new();
+    batch.version = BATCH_SETTLEMENT_VERSION;
     batch.bump = ctx.bumps.batch;
     
     // TODO: Re-enable MPC computation after fixing Arcium imports
@@ -151,15 +465,331 @@ pub fn batch_settle(
     
     emit!(BatchSettlementCreated {
         batch_id: batch.batch_id,
-        creator: ctx.accounts.creator.key(),
+        creator,
         auction_count: auction_ids.len() as u64,
     });
     
     Ok(())
 }
 
-pub fn execute_settlement(
-    ctx: Context<ExecuteSettlement>,
+/// Apply the per-auction results of a batch settlement computation.
+///
+/// Results must follow the batch's `auction_ids` order. Remaining accounts
+/// are consumed per result: the auction account, followed for results with a
+/// winner by the winning bid, its escrow, the auction's asset vault, the
+/// winner's asset account, the winner's collateral account, the creator's
+/// payment account, the treasury fee vault for the collateral mint, the
+/// asset's metadata address and the seller's stats account. When the bid
+/// names a referrer and the auction pays a referral fee, the referrer's
+/// payment account follows, then one payment account per metadata creator.
+/// Winners pay the same splits as in `execute_settlement`.
+/// Auctions without a winner (or whose winner cannot cover the price) are
+/// released from the batch and recorded as failed so they can be cancelled
+/// individually.
+pub fn apply_batch_settlement<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ApplyBatchSettlement<'info>>,
+    results: Vec<SingleAuctionResult>,
+    mpc_verification_hash: [u8; 32],
+) -> Result<()> {
+    let batch = &mut ctx.accounts.batch;
+    let protocol = &ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    // Only the protocol authority or a settlement attester can attest batch results
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::SettlementAttester,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    require!(
+        batch.status == BatchStatus::Settling,
+        ShadowProtocolError::BatchSettlementFailed
+    );
+    
+    require!(
+        results.len() == batch.auction_ids.len(),
+        ShadowProtocolError::InvalidBatchSize
+    );
+    
+    require_not_paused(protocol, PAUSE_SETTLE, AuctionType::SealedBid)?;
+    
+    let batch_key = batch.key();
+    let mut accounts = ctx.remaining_accounts.iter();
+    let mut settled_count: u64 = 0;
+    let mut failed_auction_ids: Vec<u64> = Vec::new();
+    
+    for (result, auction_id) in results.iter().zip(batch.auction_ids.iter()) {
+        require!(
+            result.auction_id == *auction_id,
+            ShadowProtocolError::AuctionNotInBatch
+        );
+        
+        let auction_info = accounts.next().ok_or(ShadowProtocolError::AuctionNotInBatch)?;
+        let mut auction: Account<'info, AuctionAccount> = Account::try_from(auction_info)?;
+        
+        require!(
+            auction.auction_id == *auction_id && auction.batch == Some(batch_key),
+            ShadowProtocolError::AuctionNotInBatch
+        );
+        
+        // Frozen auctions are left out of the batch like auctions without a winner
+        let winner = match result.winner {
+            Some(winner) if result.reserve_met && result.winning_amount > 0 && !auction.frozen => winner,
+            _ => {
+                // No eligible bid: hand the auction back for individual cancellation
+                auction.batch = None;
+                auction.exit(&crate::ID)?;
+                failed_auction_ids.push(*auction_id);
+                emit!(BatchAuctionFailed {
+                    batch_id: batch.batch_id,
+                    auction_id: *auction_id,
+                });
+                continue;
+            }
+        };
+        
+        let bid_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let escrow_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let vault_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let winner_asset_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let winner_collateral_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let creator_payment_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let fee_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let metadata_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        let seller_stats_info = accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?;
+        
+        let auction_id_bytes = auction_id.to_le_bytes();
+        
+        let mut bid: Account<'info, BidAccount> = Account::try_from(bid_info)?;
+        require!(
+            bid.auction_id == *auction_id && bid.bidder == winner,
+            ShadowProtocolError::InvalidBidAccount
+        );
+        require!(!bid.settled, ShadowProtocolError::BidAlreadySettled);
+        
+        let (escrow_key, _) = Pubkey::find_program_address(
+            &[BID_ESCROW_SEED, auction_id_bytes.as_ref(), winner.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            escrow_info.key() == escrow_key,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        let bid_escrow: Account<'info, TokenAccount> = Account::try_from(escrow_info)?;
+        
+        require!(
+            vault_info.key() == auction.asset_vault,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        let winner_asset_account: Account<'info, TokenAccount> = Account::try_from(winner_asset_info)?;
+        require!(
+            winner_asset_account.mint == auction.asset_mint && winner_asset_account.owner == winner,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        require!(
+            winner_collateral_info.key() == bid.collateral_account,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        let creator_payment_account: Account<'info, TokenAccount> = Account::try_from(creator_payment_info)?;
+        require!(
+            creator_payment_account.mint == bid_escrow.mint
+                && creator_payment_account.owner == auction.creator,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        // Fee vaults cannot be created from remaining accounts, so the vault
+        // for the collateral mint must already exist (see initialize_fee_vault)
+        let (fee_vault_key, _) = Pubkey::find_program_address(
+            &[FEE_VAULT_SEED, bid_escrow.mint.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            fee_info.key() == fee_vault_key,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        
+        let metadata = read_royalty_metadata(metadata_info, &auction.asset_mint)?;
+        
+        // Seller stats must already exist (see initialize_seller_stats)
+        let (seller_stats_key, seller_stats_bump) = Pubkey::find_program_address(
+            &[SELLER_STATS_SEED, auction.creator.as_ref(), bid_escrow.mint.as_ref()],
+            &crate::ID,
+        );
+        require!(
+            seller_stats_info.key() == seller_stats_key,
+            ShadowProtocolError::SettlementAccountMismatch
+        );
+        let mut seller_stats: Account<'info, SellerStats> = Account::try_from(seller_stats_info)?;
+        
+        let referrer_info = if bid.referrer.is_some() && auction.referral_fee_bps > 0 {
+            Some(accounts.next().ok_or(ShadowProtocolError::SettlementAccountMismatch)?.clone())
+        } else {
+            None
+        };
+        
+        let creator_count = metadata.as_ref().map_or(0, |metadata| metadata.creators.len());
+        let royalty_accounts = (0..creator_count)
+            .map(|_| accounts.next().cloned().ok_or(ShadowProtocolError::SettlementAccountMismatch.into()))
+            .collect::<Result<Vec<_>>>()?;
+        
+        // A winner whose collateral cannot cover the price fails on its own
+        if bid_escrow.amount < result.winning_amount {
+            auction.batch = None;
+            auction.exit(&crate::ID)?;
+            failed_auction_ids.push(*auction_id);
+            emit!(BatchAuctionFailed {
+                batch_id: batch.batch_id,
+                auction_id: *auction_id,
+            });
+            continue;
+        }
+        
+        let winning_amount = result.winning_amount;
+        let royalty_bps = settlement_royalty_bps(&auction, metadata.as_ref());
+        let splits = auction_settlement_splits(
+            &auction,
+            protocol,
+            &seller_stats,
+            bid.referrer,
+            royalty_bps,
+            winning_amount,
+        )?;
+        let refund = bid_escrow.amount - winning_amount;
+        
+        let auction_seeds = &[
+            AUCTION_SEED,
+            auction_id_bytes.as_ref(),
+            &[auction.bump],
+        ];
+        let auction_signer = &[&auction_seeds[..]];
+        
+        let bid_seeds = &[
+            BID_SEED,
+            auction_id_bytes.as_ref(),
+            winner.as_ref(),
+            &[bid.bump],
+        ];
+        let bid_signer = &[&bid_seeds[..]];
+        
+        // Transfer asset to winner
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault_info.clone(),
+                    to: winner_asset_info.clone(),
+                    authority: auction.to_account_info(),
+                },
+                auction_signer,
+            ),
+            auction.asset_amount,
+        )?;
+        
+        let splits = pay_settlement_splits(
+            SettlementPayment {
+                source: escrow_info.clone(),
+                authority: bid.to_account_info(),
+                seller: creator_payment_info.clone(),
+                protocol_fee_vault: fee_info.clone(),
+                referrer: referrer_info,
+                royalty_accounts: &royalty_accounts,
+                payment_mint: bid_escrow.mint,
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            splits,
+            metadata.as_ref(),
+            bid.referrer,
+            *auction_id,
+            bid_signer,
+        )?;
+        
+        // Refund collateral above the price
+        if refund > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: escrow_info.clone(),
+                        to: winner_collateral_info.clone(),
+                        authority: bid.to_account_info(),
+                    },
+                    bid_signer,
+                ),
+                refund,
+            )?;
+        }
+        
+        bid.is_winner = true;
+        bid.settled = true;
+        bid.exit(&crate::ID)?;
+        
+        auction.winner = Some(winner);
+        auction.winning_amount = winning_amount;
+        auction.mpc_verification_hash = Some(mpc_verification_hash);
+        auction.settlement_authorized = true;
+        auction.status = AuctionStatus::Settled;
+        auction.settled_at = Some(clock.unix_timestamp);
+        auction.exit(&crate::ID)?;
+        
+        record_seller_sale(&mut seller_stats, auction.creator, winning_amount, seller_stats_bump);
+        seller_stats.exit(&crate::ID)?;
+        
+        settled_count += 1;
+        
+        emit!(AuctionSettled {
+            auction_id: *auction_id,
+            winner: Some(winner),
+            winning_amount,
+            splits: Some(splits),
+            settled_at: clock.unix_timestamp,
+        });
+    }
+    
+    batch.failed_auction_ids = failed_auction_ids;
+    batch.status = if settled_count > 0 {
+        BatchStatus::Settled
+    } else {
+        BatchStatus::Failed
+    };
+    batch.settled_at = Some(clock.unix_timestamp);
+    
+    emit!(BatchSettled {
+        batch_id: batch.batch_id,
+        settled_count,
+        settled_at: clock.unix_timestamp,
+    });
+    
+    msg!("Batch {} settled {} of {} auctions",
+         batch.batch_id, settled_count, batch.auction_ids.len());
+    
+    Ok(())
+}
+
+/// Close a finished batch settlement and return its rent to the creator
+pub fn close_batch(ctx: Context<CloseBatch>, batch_id: u64) -> Result<()> {
+    let batch = &ctx.accounts.batch;
+    
+    require!(
+        batch.status == BatchStatus::Settled || batch.status == BatchStatus::Failed,
+        ShadowProtocolError::BatchNotFinalized
+    );
+    
+    msg!("Batch {} closed", batch_id);
+    
+    Ok(())
+}
+
+/// Pay out a settled auction. Beyond the seller and protocol fee, the price
+/// is split with the bid's referrer (when the winning bid names one) and
+/// with the asset's creators (when the asset has Metaplex metadata). Creator
+/// payment accounts are passed as remaining accounts in metadata order.
+pub fn execute_settlement<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteSettlement<'info>>,
     auction_id: u64,
     winner: Pubkey,
     winning_amount: u64,
@@ -167,7 +797,9 @@ pub fn execute_settlement(
     let auction = &mut ctx.accounts.auction;
     let protocol = &ctx.accounts.protocol_state;
     
-    require!(!protocol.paused, ShadowProtocolError::ProtocolPaused);
+    require_not_paused(protocol, PAUSE_SETTLE, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
     
     // Check that settlement is authorized
     require!(
@@ -185,15 +817,21 @@ pub fn execute_settlement(
         ShadowProtocolError::InvalidAssetAmount
     );
     
+    // Public USD reserve, converted at the oracle price at settlement
+    if let Some(pricing) = &auction.usd_pricing {
+        let reserve = usd_to_payment_amount(pricing, ctx.accounts.price_feed.as_ref(), pricing.reserve_usd)?;
+        require!(winning_amount >= reserve, ShadowProtocolError::ReserveNotMet);
+    }
+    
     // Validate asset vault has sufficient funds and matches auction amount
     require!(
         ctx.accounts.asset_vault.amount >= auction.asset_amount,
         ShadowProtocolError::InvalidAssetAmount
     );
     
-    // Validate winner is provided
+    // Validate winner is provided and is the paying signer
     require!(
-        winner != Pubkey::default(),
+        winner != Pubkey::default() && winner == ctx.accounts.winner.key(),
         ShadowProtocolError::InvalidWinnerDetermination
     );
     
@@ -202,16 +840,37 @@ pub fn execute_settlement(
     auction.status = AuctionStatus::Settled;
     auction.settled_at = Some(Clock::get()?.unix_timestamp);
     
-    // Calculate protocol fee
-    let fee_amount = winning_amount
-        .checked_mul(protocol.protocol_fee as u64)
-        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?
-        .checked_div(10000)
-        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    // Referral fee only applies when the winning bid names a referrer.
+    // Sealed-bid wins must pass the bid; Dutch wins have none.
+    let referrer = match &ctx.accounts.winning_bid {
+        Some(bid) => bid.referrer,
+        None => {
+            require!(
+                auction.auction_type == AuctionType::Dutch,
+                ShadowProtocolError::SettlementAccountMismatch
+            );
+            None
+        }
+    };
     
-    let transfer_amount = winning_amount
-        .checked_sub(fee_amount)
-        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    let metadata = read_royalty_metadata(&ctx.accounts.asset_metadata, &auction.asset_mint)?;
+    
+    // NFT listings always pay creator royalties
+    require!(
+        auction.nft.is_none() || metadata.is_some(),
+        ShadowProtocolError::InvalidMetadataAccount
+    );
+    let royalty_bps = settlement_royalty_bps(auction, metadata.as_ref());
+    
+    let seller_stats = &mut ctx.accounts.seller_stats;
+    let splits = auction_settlement_splits(
+        auction,
+        protocol,
+        seller_stats,
+        referrer,
+        royalty_bps,
+        winning_amount,
+    )?;
     
     // Transfer asset to winner
     let auction_id_bytes = auction.auction_id.to_le_bytes();
@@ -222,155 +881,768 @@ pub fn execute_settlement(
     ];
     let signer_seeds = &[&auction_seeds[..]];
     
-    transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.asset_vault.to_account_info(),
-                to: ctx.accounts.winner_asset_account.to_account_info(),
+    // Remaining accounts: one royalty account per creator, then the
+    // programmable NFT transfer accounts when the asset is a pNFT
+    let creator_count = metadata.as_ref().map_or(0, |metadata| metadata.creators.len());
+    require!(
+        ctx.remaining_accounts.len() >= creator_count,
+        ShadowProtocolError::SettlementAccountMismatch
+    );
+    let (royalty_accounts, nft_transfer_accounts) = ctx.remaining_accounts.split_at(creator_count);
+    
+    if auction.nft.is_some_and(|listing| listing.programmable) {
+        transfer_programmable_nft(
+            NftTransferAccounts {
+                token: ctx.accounts.asset_vault.to_account_info(),
+                token_owner: auction.to_account_info(),
+                destination: ctx.accounts.winner_asset_account.to_account_info(),
+                destination_owner: ctx.accounts.winner.to_account_info(),
+                mint: ctx.accounts.asset_mint.to_account_info(),
+                metadata: ctx.accounts.asset_metadata.to_account_info(),
                 authority: auction.to_account_info(),
+                payer: ctx.accounts.winner.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                extra: nft_transfer_accounts,
             },
+            auction.asset_amount,
             signer_seeds,
-        ),
-        auction.asset_amount, // Use the stored asset amount
-    )?;
-    
-    // Transfer payment from winner to creator (minus fees)
-    transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.winner_payment_account.to_account_info(),
-                to: ctx.accounts.creator_payment_account.to_account_info(),
-                authority: ctx.accounts.winner.to_account_info(),
-            },
-        ),
-        transfer_amount,
-    )?;
-    
-    // Transfer fee to protocol
-    if fee_amount > 0 {
+        )?;
+    } else {
+        require!(
+            nft_transfer_accounts.is_empty(),
+            ShadowProtocolError::SettlementAccountMismatch
+        );
         transfer(
-            CpiContext::new(
+            CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.winner_payment_account.to_account_info(),
-                    to: ctx.accounts.protocol_fee_account.to_account_info(),
-                    authority: ctx.accounts.winner.to_account_info(),
+                    from: ctx.accounts.asset_vault.to_account_info(),
+                    to: ctx.accounts.winner_asset_account.to_account_info(),
+                    authority: auction.to_account_info(),
                 },
+                signer_seeds,
             ),
-            fee_amount,
+            auction.asset_amount, // Use the stored asset amount
         )?;
     }
     
+    let splits = pay_settlement_splits(
+        SettlementPayment {
+            source: ctx.accounts.winner_payment_account.to_account_info(),
+            authority: ctx.accounts.winner.to_account_info(),
+            seller: ctx.accounts.creator_payment_account.to_account_info(),
+            protocol_fee_vault: ctx.accounts.protocol_fee_account.to_account_info(),
+            referrer: ctx.accounts.referrer_payment_account.as_ref().map(|account| account.to_account_info()),
+            royalty_accounts,
+            payment_mint: ctx.accounts.payment_mint.key(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        splits,
+        metadata.as_ref(),
+        referrer,
+        auction_id,
+        &[],
+    )?;
+    
+    record_seller_sale(seller_stats, auction.creator, winning_amount, ctx.bumps.seller_stats);
+    
     emit!(AuctionSettled {
         auction_id,
         winner: Some(winner),
         winning_amount,
+        splits: Some(splits),
         settled_at: Clock::get()?.unix_timestamp,
     });
     
     Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(auction_id: u64)]
-pub struct AuthorizeSettlement<'info> {
-    pub authority: Signer<'info>,
+/// Record the uniform clearing price and per-bid unit allocations computed
+/// by the multi-unit MPC circuit. Bid accounts are passed as remaining
+/// accounts in the same order as `allocations`; large auctions may be
+/// recorded across several calls with the same clearing price and hash.
+pub fn authorize_multi_unit_settlement<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AuthorizeMultiUnitSettlement<'info>>,
+    auction_id: u64,
+    clearing_price: u64,
+    allocations: Vec<u64>,
+    mpc_verification_hash: [u8; 32],
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
     
-    #[account(
-        mut,
-        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
-        bump = auction.bump
-    )]
-    pub auction: Account<'info, AuctionAccount>,
+    // Only the protocol authority or a settlement attester can authorize settlement
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::SettlementAttester,
+        ctx.accounts.authority.key(),
+    )?;
     
-    #[account(
-        seeds = [PROTOCOL_SEED],
-        bump = protocol_state.bump
-    )]
-    pub protocol_state: Account<'info, ProtocolState>,
-}
-
-#[derive(Accounts)]
-#[instruction(auction_id: u64)]
-pub struct SettleAuction<'info> {
-    #[account(mut)]
-    pub settler: Signer<'info>,
+    require!(
+        auction.auction_type == AuctionType::MultiUnit,
+        ShadowProtocolError::InvalidAuctionType
+    );
     
-    #[account(
-        mut,
-        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
-        bump = auction.bump
-    )]
-    pub auction: Account<'info, AuctionAccount>,
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
     
-    #[account(
-        seeds = [PROTOCOL_SEED],
-        bump = protocol_state.bump
-    )]
-    pub protocol_state: Account<'info, ProtocolState>,
+    require!(
+        auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
     
-    // TODO: Re-enable after fixing Arcium imports
-    // Arcium accounts
-    // pub computation_definition: Account<'info, ComputationDefinitionAccount>,
-    // pub mxe: Account<'info, PersistentMXEAccount>,
+    require!(
+        allocations.len() == ctx.remaining_accounts.len(),
+        ShadowProtocolError::InvalidWinnerDetermination
+    );
     
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct BatchSettle<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+    if auction.settlement_authorized {
+        // Continuation of an earlier call must carry the same result
+        require!(
+            auction.clearing_price == clearing_price
+                && auction.mpc_verification_hash == Some(mpc_verification_hash),
+            ShadowProtocolError::MpcVerificationFailed
+        );
+    } else {
+        require!(
+            clearing_price >= auction.minimum_bid,
+            ShadowProtocolError::BidBelowMinimum
+        );
+        
+        auction.clearing_price = clearing_price;
+        auction.mpc_verification_hash = Some(mpc_verification_hash);
+        auction.settlement_authorized = true;
+    }
     
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + BatchSettlement::INIT_SPACE,
-        seeds = [BATCH_SEED, creator.key().as_ref()],
-        bump
-    )]
-    pub batch: Account<'info, BatchSettlement>,
+    for (bid_info, units) in ctx.remaining_accounts.iter().zip(allocations.iter()) {
+        let mut bid: Account<'info, BidAccount> = Account::try_from(bid_info)?;
+        
+        require!(
+            bid.auction_id == auction_id,
+            ShadowProtocolError::InvalidBidAccount
+        );
+        
+        require!(!bid.settled, ShadowProtocolError::BidAlreadySettled);
+        
+        // Payment for the allocation must be covered by escrowed collateral
+        let payment = units
+            .checked_mul(clearing_price)
+            .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+        require!(
+            payment <= bid.collateral_amount,
+            ShadowProtocolError::InsufficientCollateral
+        );
+        
+        // Replace any allocation recorded for this bid by an earlier call
+        auction.units_allocated = auction.units_allocated
+            .checked_sub(bid.units_allocated)
+            .and_then(|allocated| allocated.checked_add(*units))
+            .ok_or(ShadowProtocolError::AllocationExceedsSupply)?;
+        
+        if !bid.allocation_recorded {
+            auction.allocations_recorded = auction.allocations_recorded
+                .checked_add(1)
+                .ok_or(ShadowProtocolError::InvalidWinnerDetermination)?;
+            bid.allocation_recorded = true;
+        }
+        
+        bid.units_allocated = *units;
+        bid.is_winner = *units > 0;
+        bid.exit(&crate::ID)?;
+    }
     
-    #[account(
-        seeds = [PROTOCOL_SEED],
-        bump = protocol_state.bump
-    )]
-    pub protocol_state: Account<'info, ProtocolState>,
+    require!(
+        auction.units_allocated <= auction.total_units,
+        ShadowProtocolError::AllocationExceedsSupply
+    );
     
-    // TODO: Re-enable after fixing Arcium imports
-    // Arcium accounts
-    // pub computation_definition: Account<'info, ComputationDefinitionAccount>,
-    // pub mxe: Account<'info, PersistentMXEAccount>,
+    emit!(MultiUnitAuctionCleared {
+        auction_id,
+        clearing_price,
+        units_allocated: auction.units_allocated,
+        total_units: auction.total_units,
+    });
     
-    pub system_program: Program<'info, System>,
+    msg!("Multi-unit auction {} cleared at {} per unit, {} units allocated",
+         auction_id, clearing_price, auction.units_allocated);
+    
+    Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(auction_id: u64)]
-pub struct ExecuteSettlement<'info> {
-    #[account(mut)]
-    pub winner: Signer<'info>,
+/// Settle one bid of a cleared multi-unit auction: deliver allocated units,
+/// pay the seller and protocol from escrow, and refund unused collateral.
+/// Settling the last bid returns any unsold units to the creator.
+pub fn settle_multi_unit_bid(
+    ctx: Context<SettleMultiUnitBid>,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let bid = &mut ctx.accounts.bid;
     
-    #[account(
-        mut,
-        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
-        bump = auction.bump
-    )]
-    pub auction: Account<'info, AuctionAccount>,
+    require!(
+        auction.auction_type == AuctionType::MultiUnit,
+        ShadowProtocolError::InvalidAuctionType
+    );
     
-    #[account(
-        seeds = [PROTOCOL_SEED],
-        bump = protocol_state.bump
-    )]
+    require_not_paused(&ctx.accounts.protocol_state, PAUSE_SETTLE, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.settlement_authorized,
+        ShadowProtocolError::SettlementNotAuthorized
+    );
+    
+    // Authorization may span several calls; settling before every bid has
+    // its allocation would refund bids that are still due units
+    require!(
+        auction.allocations_recorded == auction.bid_count,
+        ShadowProtocolError::AllocationIncomplete
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(!bid.settled, ShadowProtocolError::BidAlreadySettled);
+    
+    let unit_size = auction.asset_amount
+        .checked_div(auction.total_units)
+        .ok_or(ShadowProtocolError::InvalidUnitCount)?;
+    
+    let units = bid.units_allocated;
+    let asset_amount = units
+        .checked_mul(unit_size)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    let payment = units
+        .checked_mul(auction.clearing_price)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    let fee_amount = calculate_protocol_fee(payment, auction.protocol_fee_bps)?;
+    let seller_amount = payment
+        .checked_sub(fee_amount)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    let refund = ctx.accounts.bid_escrow.amount
+        .checked_sub(payment)
+        .ok_or(ShadowProtocolError::InsufficientCollateral)?;
+    
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let auction_signer = &[&auction_seeds[..]];
+    
+    let bidder_key = bid.bidder;
+    let bid_seeds = &[
+        BID_SEED,
+        auction_id_bytes.as_ref(),
+        bidder_key.as_ref(),
+        &[bid.bump],
+    ];
+    let bid_signer = &[&bid_seeds[..]];
+    
+    // Deliver allocated units to the bidder
+    if asset_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.asset_vault.to_account_info(),
+                    to: ctx.accounts.bidder_asset_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                auction_signer,
+            ),
+            asset_amount,
+        )?;
+    }
+    
+    // Pay the seller from escrow (minus fees)
+    if seller_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bid_escrow.to_account_info(),
+                    to: ctx.accounts.creator_payment_account.to_account_info(),
+                    authority: bid.to_account_info(),
+                },
+                bid_signer,
+            ),
+            seller_amount,
+        )?;
+    }
+    
+    // Transfer fee to protocol
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bid_escrow.to_account_info(),
+                    to: ctx.accounts.protocol_fee_account.to_account_info(),
+                    authority: bid.to_account_info(),
+                },
+                bid_signer,
+            ),
+            fee_amount,
+        )?;
+        
+        emit!(ProtocolFeeCollected {
+            auction_id,
+            mint: ctx.accounts.protocol_fee_account.mint,
+            amount: fee_amount,
+        });
+    }
+    
+    // Refund unused collateral
+    if refund > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.bid_escrow.to_account_info(),
+                    to: ctx.accounts.bidder_collateral_account.to_account_info(),
+                    authority: bid.to_account_info(),
+                },
+                bid_signer,
+            ),
+            refund,
+        )?;
+    }
+    
+    bid.settled = true;
+    bid.is_winner = units > 0;
+    auction.bids_settled = auction.bids_settled
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    
+    emit!(BidSettled {
+        auction_id,
+        bidder: bidder_key,
+        units,
+        payment,
+        refund,
+    });
+    
+    // Once every bid is settled, return unsold units and close out the auction
+    if auction.bids_settled == auction.bid_count {
+        let unsold_amount = auction.total_units
+            .checked_sub(auction.units_allocated)
+            .ok_or(ShadowProtocolError::AllocationExceedsSupply)?
+            .checked_mul(unit_size)
+            .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+        
+        if unsold_amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.asset_vault.to_account_info(),
+                        to: ctx.accounts.creator_asset_account.to_account_info(),
+                        authority: auction.to_account_info(),
+                    },
+                    auction_signer,
+                ),
+                unsold_amount,
+            )?;
+        }
+        
+        let settled_at = Clock::get()?.unix_timestamp;
+        auction.status = AuctionStatus::Settled;
+        auction.settled_at = Some(settled_at);
+        
+        emit!(AuctionSettled {
+            auction_id,
+            winner: None,
+            winning_amount: auction.clearing_price,
+            splits: None,
+            settled_at,
+        });
+    }
+    
+    Ok(())
+}
+
+/// Supplier payout under the auction's pricing rule. A second-price auction
+/// with no runner-up pays the winner's own ask rather than the ceiling.
+pub(crate) fn reverse_auction_payout(
+    pricing_rule: PricingRule,
+    winning_ask: u64,
+    runner_up_ask: Option<u64>,
+) -> Result<u64> {
+    if let Some(runner_up_ask) = runner_up_ask {
+        require!(
+            runner_up_ask >= winning_ask,
+            ShadowProtocolError::InvalidWinnerDetermination
+        );
+    }
+    
+    Ok(match pricing_rule {
+        PricingRule::FirstPrice => winning_ask,
+        PricingRule::SecondPrice => runner_up_ask.unwrap_or(winning_ask),
+    })
+}
+
+/// Record the result of a reverse auction computed by the MPC circuit: the
+/// winning supplier, its ask and the runner-up ask. The payout is derived
+/// here from the auction's pricing rule and bounded by the budget, so
+/// `execute_reverse_settlement` takes no amount.
+pub fn authorize_reverse_settlement(
+    ctx: Context<AuthorizeReverseSettlement>,
+    auction_id: u64,
+    winning_ask: u64,
+    runner_up_ask: Option<u64>,
+    mpc_verification_hash: [u8; 32],
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let protocol = &ctx.accounts.protocol_state;
+    
+    // Only the protocol authority or a settlement attester can attest the result
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::SettlementAttester,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    require!(
+        auction.auction_type == AuctionType::Reverse,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    require!(
+        !auction.settlement_authorized,
+        ShadowProtocolError::AuctionAlreadySettled
+    );
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(winning_ask > 0, ShadowProtocolError::InvalidAssetAmount);
+    
+    let payout = reverse_auction_payout(auction.pricing_rule, winning_ask, runner_up_ask)?;
+    require!(payout <= auction.budget, ShadowProtocolError::BudgetExceeded);
+    
+    let supplier = ctx.accounts.winning_bid.bidder;
+    auction.winner = Some(supplier);
+    auction.winning_amount = payout;
+    auction.mpc_verification_hash = Some(mpc_verification_hash);
+    auction.settlement_authorized = true;
+    
+    msg!("Reverse auction {} awarded to {} for {}", auction_id, supplier, payout);
+    
+    Ok(())
+}
+
+/// Award a reverse auction to the supplier recorded by
+/// `authorize_reverse_settlement`: the supplier's escrowed asset goes to the
+/// buyer, the supplier is paid the recorded payout from the budget vault,
+/// and any unspent budget is returned to the buyer. Anyone may crank this
+/// once the result is recorded.
+pub fn execute_reverse_settlement(
+    ctx: Context<ExecuteReverseSettlement>,
+    auction_id: u64,
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+    let winning_bid = &mut ctx.accounts.winning_bid;
+    let protocol = &ctx.accounts.protocol_state;
+    
+    require_not_paused(protocol, PAUSE_SETTLE, auction.auction_type)?;
+    
+    require!(!auction.frozen, ShadowProtocolError::AuctionFrozen);
+    
+    require!(
+        auction.auction_type == AuctionType::Reverse,
+        ShadowProtocolError::InvalidAuctionType
+    );
+    
+    require!(
+        auction.settlement_authorized,
+        ShadowProtocolError::SettlementNotAuthorized
+    );
+    
+    require!(
+        auction.status == AuctionStatus::Ended,
+        ShadowProtocolError::InvalidAuctionStatus
+    );
+    
+    let winning_amount = auction.winning_amount;
+    require!(
+        winning_amount <= ctx.accounts.budget_vault.amount,
+        ShadowProtocolError::BudgetExceeded
+    );
+    
+    require!(!winning_bid.settled, ShadowProtocolError::BidAlreadySettled);
+    
+    let fee_amount = calculate_protocol_fee(winning_amount, auction.protocol_fee_bps)?;
+    let supplier_amount = winning_amount
+        .checked_sub(fee_amount)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    let unspent_budget = ctx.accounts.budget_vault.amount
+        .checked_sub(winning_amount)
+        .ok_or(ShadowProtocolError::BudgetExceeded)?;
+    
+    let auction_id_bytes = auction_id.to_le_bytes();
+    let auction_seeds = &[
+        AUCTION_SEED,
+        auction_id_bytes.as_ref(),
+        &[auction.bump],
+    ];
+    let auction_signer = &[&auction_seeds[..]];
+    
+    let supplier = winning_bid.bidder;
+    let bid_seeds = &[
+        BID_SEED,
+        auction_id_bytes.as_ref(),
+        supplier.as_ref(),
+        &[winning_bid.bump],
+    ];
+    let bid_signer = &[&bid_seeds[..]];
+    
+    // Deliver the supplier's escrowed asset to the buyer
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.winner_escrow.to_account_info(),
+                to: ctx.accounts.creator_asset_account.to_account_info(),
+                authority: winning_bid.to_account_info(),
+            },
+            bid_signer,
+        ),
+        ctx.accounts.winner_escrow.amount,
+    )?;
+    
+    // Pay the supplier from the budget (minus fees)
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.budget_vault.to_account_info(),
+                to: ctx.accounts.winner_payment_account.to_account_info(),
+                authority: auction.to_account_info(),
+            },
+            auction_signer,
+        ),
+        supplier_amount,
+    )?;
+    
+    // Transfer fee to protocol
+    if fee_amount > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.budget_vault.to_account_info(),
+                    to: ctx.accounts.protocol_fee_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                auction_signer,
+            ),
+            fee_amount,
+        )?;
+        
+        emit!(ProtocolFeeCollected {
+            auction_id,
+            mint: ctx.accounts.protocol_fee_account.mint,
+            amount: fee_amount,
+        });
+    }
+    
+    // Return unspent budget to the buyer
+    if unspent_budget > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.budget_vault.to_account_info(),
+                    to: ctx.accounts.creator_payment_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                auction_signer,
+            ),
+            unspent_budget,
+        )?;
+    }
+    
+    let settled_at = Clock::get()?.unix_timestamp;
+    auction.status = AuctionStatus::Settled;
+    auction.settled_at = Some(settled_at);
+    winning_bid.is_winner = true;
+    winning_bid.settled = true;
+    
+    emit!(AuctionSettled {
+        auction_id,
+        winner: Some(supplier),
+        winning_amount,
+        splits: Some(SettlementSplits {
+            protocol_fee: fee_amount,
+            seller_amount: supplier_amount,
+            ..SettlementSplits::default()
+        }),
+        settled_at,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct AuthorizeSettlement<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct SettleAuction<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    // TODO: Re-enable after fixing Arcium imports
+    // Arcium accounts
+    // pub computation_definition: Account<'info, ComputationDefinitionAccount>,
+    // pub mxe: Account<'info, PersistentMXEAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BatchSettle<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BatchSettlement::INIT_SPACE,
+        seeds = [BATCH_SEED, protocol_state.next_batch_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub batch: Account<'info, BatchSettlement>,
+    
+    #[account(
+        mut,
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    // TODO: Re-enable after fixing Arcium imports
+    // Arcium accounts
+    // pub computation_definition: Account<'info, ComputationDefinitionAccount>,
+    // pub mxe: Account<'info, PersistentMXEAccount>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyBatchSettlement<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [BATCH_SEED, batch.batch_id.to_le_bytes().as_ref()],
+        bump = batch.bump
+    )]
+    pub batch: Account<'info, BatchSettlement>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(batch_id: u64)]
+pub struct CloseBatch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [BATCH_SEED, batch_id.to_le_bytes().as_ref()],
+        bump = batch.bump,
+        has_one = creator @ ShadowProtocolError::Unauthorized,
+        close = creator
+    )]
+    pub batch: Account<'info, BatchSettlement>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct ExecuteSettlement<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
     pub protocol_state: Account<'info, ProtocolState>,
     
     /// Asset vault holding the auctioned item
     #[account(
         mut,
-        associated_token::mint = auction.asset_mint,
-        associated_token::authority = auction
+        seeds = [ASSET_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
     )]
     pub asset_vault: Account<'info, TokenAccount>,
     
@@ -383,17 +1655,298 @@ pub struct ExecuteSettlement<'info> {
     pub winner_asset_account: Account<'info, TokenAccount>,
     
     /// Winner's payment account
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = auction.payment_mint,
+        token::authority = winner
+    )]
     pub winner_payment_account: Account<'info, TokenAccount>,
     
     /// Creator's payment account
+    #[account(
+        mut,
+        token::mint = auction.payment_mint,
+        token::authority = auction.creator
+    )]
+    pub creator_payment_account: Account<'info, TokenAccount>,
+    
+    /// Payment token mint
+    #[account(address = auction.payment_mint @ ShadowProtocolError::InvalidPaymentMint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    /// Treasury PDA that owns the per-mint fee vaults
+    /// CHECK: PDA used only as the fee vault authority
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Treasury fee vault for the payment mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = winner,
+        token::mint = payment_mint,
+        token::authority = treasury,
+        seeds = [FEE_VAULT_SEED, payment_mint.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+    
+    /// Winning sealed bid, which carries the referrer (required unless the
+    /// auction is Dutch, whose wins have no bid account)
+    #[account(
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), winner.key().as_ref()],
+        bump = winning_bid.bump
+    )]
+    pub winning_bid: Option<Account<'info, BidAccount>>,
+    
+    /// Referrer's payment account (required when a referral fee is owed)
+    #[account(mut)]
+    pub referrer_payment_account: Option<Account<'info, TokenAccount>>,
+    
+    /// Oracle price account (required when the auction has USD pricing)
+    /// CHECK: Matched against the auction's price feed and parsed in the handler
+    pub price_feed: Option<UncheckedAccount<'info>>,
+    
+    /// Metaplex metadata address for the asset mint, which pays creator
+    /// royalties when initialized
+    /// CHECK: Owner, address and layout are validated in the handler
+    #[account(mut)]
+    pub asset_metadata: UncheckedAccount<'info>,
+    
+    /// Asset mint
+    #[account(address = auction.asset_mint)]
+    pub asset_mint: Account<'info, Mint>,
+    
+    /// Seller's settled volume in the payment mint, used for fee tiers
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + SellerStats::INIT_SPACE,
+        seeds = [SELLER_STATS_SEED, auction.creator.as_ref(), payment_mint.key().as_ref()],
+        bump
+    )]
+    pub seller_stats: Account<'info, SellerStats>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct AuthorizeMultiUnitSettlement<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct SettleMultiUnitBid<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    #[account(
+        mut,
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), bid.bidder.as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, BidAccount>,
+    
+    /// Bid escrow holding the bidder's collateral
+    #[account(
+        mut,
+        seeds = [BID_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), bid.bidder.as_ref()],
+        bump
+    )]
+    pub bid_escrow: Account<'info, TokenAccount>,
+    
+    /// Asset vault holding the auctioned units
+    #[account(
+        mut,
+        seeds = [ASSET_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub asset_vault: Account<'info, TokenAccount>,
+    
+    /// Bidder's asset account receiving allocated units
+    #[account(
+        mut,
+        associated_token::mint = auction.asset_mint,
+        associated_token::authority = bid.bidder
+    )]
+    pub bidder_asset_account: Account<'info, TokenAccount>,
+    
+    /// Bidder's collateral account receiving the refund
+    #[account(
+        mut,
+        address = bid.collateral_account
+    )]
+    pub bidder_collateral_account: Account<'info, TokenAccount>,
+    
+    /// Creator's asset account receiving unsold units
+    #[account(
+        mut,
+        associated_token::mint = auction.asset_mint,
+        associated_token::authority = auction.creator
+    )]
+    pub creator_asset_account: Account<'info, TokenAccount>,
+    
+    /// Creator's payment account
+    #[account(
+        mut,
+        token::mint = auction.payment_mint,
+        token::authority = auction.creator
+    )]
+    pub creator_payment_account: Account<'info, TokenAccount>,
+    
+    /// Payment token mint
+    #[account(address = auction.payment_mint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    /// Treasury PDA that owns the per-mint fee vaults
+    /// CHECK: PDA used only as the fee vault authority
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Treasury fee vault for the payment mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = settler,
+        token::mint = payment_mint,
+        token::authority = treasury,
+        seeds = [FEE_VAULT_SEED, payment_mint.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct ExecuteReverseSettlement<'info> {
     #[account(mut)]
+    pub settler: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Ask of the supplier recorded as the winner
+    #[account(
+        mut,
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), winning_bid.bidder.as_ref()],
+        bump = winning_bid.bump,
+        constraint = auction.winner == Some(winning_bid.bidder) @ ShadowProtocolError::InvalidBidAccount
+    )]
+    pub winning_bid: Account<'info, BidAccount>,
+    
+    /// Escrow holding the winning supplier's asset
+    #[account(
+        mut,
+        seeds = [BID_ESCROW_SEED, auction_id.to_le_bytes().as_ref(), winning_bid.bidder.as_ref()],
+        bump
+    )]
+    pub winner_escrow: Account<'info, TokenAccount>,
+    
+    /// Vault holding the buyer's budget
+    #[account(
+        mut,
+        seeds = [BUDGET_VAULT_SEED, auction_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub budget_vault: Account<'info, TokenAccount>,
+    
+    /// Buyer's account receiving the procured asset
+    #[account(
+        mut,
+        associated_token::mint = auction.asset_mint,
+        associated_token::authority = auction.creator
+    )]
+    pub creator_asset_account: Account<'info, TokenAccount>,
+    
+    /// Buyer's account receiving unspent budget
+    #[account(
+        mut,
+        token::mint = auction.payment_mint,
+        token::authority = auction.creator
+    )]
     pub creator_payment_account: Account<'info, TokenAccount>,
     
-    /// Protocol fee account
+    /// Supplier's account receiving payment
     #[account(
         mut,
-        address = protocol_state.fee_recipient
+        token::mint = auction.payment_mint,
+        token::authority = winning_bid.bidder
+    )]
+    pub winner_payment_account: Account<'info, TokenAccount>,
+    
+    /// Payment token mint
+    #[account(address = auction.payment_mint)]
+    pub payment_mint: Account<'info, Mint>,
+    
+    /// Treasury PDA that owns the per-mint fee vaults
+    /// CHECK: PDA used only as the fee vault authority
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Treasury fee vault for the payment mint (created on first use)
+    #[account(
+        init_if_needed,
+        payer = settler,
+        token::mint = payment_mint,
+        token::authority = treasury,
+        seeds = [FEE_VAULT_SEED, payment_mint.key().as_ref()],
+        bump
     )]
     pub protocol_fee_account: Account<'info, TokenAccount>,
     
@@ -401,6 +1954,39 @@ pub struct ExecuteSettlement<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct AuthorizeReverseSettlement<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [AUCTION_SEED, auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, AuctionAccount>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    /// Ask of the winning supplier
+    #[account(
+        seeds = [BID_SEED, auction_id.to_le_bytes().as_ref(), winning_bid.bidder.as_ref()],
+        bump = winning_bid.bump
+    )]
+    pub winning_bid: Account<'info, BidAccount>,
+}
+
 // TODO: Re-enable after fixing Arcium imports
 // impl<'info> SettleAuction<'info> {
 //     pub fn queue_computation_ctx(&self) -> CpiContext<'_, '_, '_, 'info, arcium_anchor::QueueComputation<'info>> {
@@ -0,0 +1,286 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount, Mint, Transfer, transfer};
+use crate::state::*;
+use crate::error::ShadowProtocolError;
+use super::admin::require_role;
+
+/// Create the treasury fee vault for a mint ahead of its first fee
+/// (permissionless). Settlement paths that take their accounts as remaining
+/// accounts cannot create the vault themselves.
+pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+    msg!("Fee vault ready for mint {}", ctx.accounts.mint.key());
+    
+    Ok(())
+}
+
+/// Create a seller's volume stats for a payment mint ahead of their first
+/// sale (permissionless). Batch settlement takes its accounts as remaining
+/// accounts and cannot create them itself.
+pub fn initialize_seller_stats(ctx: Context<InitializeSellerStats>) -> Result<()> {
+    let seller_stats = &mut ctx.accounts.seller_stats;
+    seller_stats.seller = ctx.accounts.seller.key();
+    seller_stats.bump = ctx.bumps.seller_stats;
+    
+    msg!("Seller stats ready for {} in mint {}", seller_stats.seller, ctx.accounts.mint.key());
+    
+    Ok(())
+}
+
+/// Withdraw collected fees for one mint to the protocol fee recipient
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::FeeManager,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    require!(amount > 0, ShadowProtocolError::InvalidWithdrawalAmount);
+    
+    require!(
+        ctx.accounts.fee_vault.amount >= amount,
+        ShadowProtocolError::InsufficientTreasuryBalance
+    );
+    
+    let treasury_seeds = &[
+        TREASURY_SEED,
+        &[ctx.bumps.treasury],
+    ];
+    let signer = &[&treasury_seeds[..]];
+    
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.fee_vault.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+    
+    let ledger = &mut ctx.accounts.fee_ledger;
+    ledger.mint = ctx.accounts.mint.key();
+    ledger.total_withdrawn = ledger.total_withdrawn
+        .checked_add(amount)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    ledger.withdrawal_count = ledger.withdrawal_count
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    ledger.last_withdrawal_at = Some(clock.unix_timestamp);
+    ledger.bump = ctx.bumps.fee_ledger;
+    
+    emit!(FeesWithdrawn {
+        mint: ledger.mint,
+        amount,
+        destination: ctx.accounts.destination.key(),
+        total_withdrawn: ledger.total_withdrawn,
+        remaining_balance: ctx.accounts.fee_vault.amount - amount,
+        withdrawn_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Withdrew {} fees of mint {}", amount, ledger.mint);
+    
+    Ok(())
+}
+
+/// Withdraw protocol fees collected in native SOL to the fee recipient
+pub fn withdraw_sol_fees(ctx: Context<WithdrawSolFees>, amount: u64) -> Result<()> {
+    let protocol = &ctx.accounts.protocol_state;
+    let clock = Clock::get()?;
+    
+    require_role(
+        protocol,
+        ctx.accounts.roles.as_deref(),
+        Role::FeeManager,
+        ctx.accounts.authority.key(),
+    )?;
+    
+    require!(amount > 0, ShadowProtocolError::InvalidWithdrawalAmount);
+    
+    // The vault keeps its rent-exempt minimum; everything above it is fees
+    let vault_info = ctx.accounts.sol_fee_vault.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(vault_info.data_len());
+    let available = vault_info.lamports().saturating_sub(rent_exempt);
+    require!(
+        available >= amount,
+        ShadowProtocolError::InsufficientTreasuryBalance
+    );
+    
+    let ledger = &mut ctx.accounts.sol_fee_vault;
+    ledger.sub_lamports(amount)?;
+    ctx.accounts.fee_recipient.add_lamports(amount)?;
+    
+    ledger.total_withdrawn = ledger.total_withdrawn
+        .checked_add(amount)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    ledger.withdrawal_count = ledger.withdrawal_count
+        .checked_add(1)
+        .ok_or(ShadowProtocolError::FeeCalculationOverflow)?;
+    ledger.last_withdrawal_at = Some(clock.unix_timestamp);
+    
+    emit!(FeesWithdrawn {
+        mint: NATIVE_SOL_MINT,
+        amount,
+        destination: ctx.accounts.fee_recipient.key(),
+        total_withdrawn: ledger.total_withdrawn,
+        remaining_balance: available - amount,
+        withdrawn_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Withdrew {} lamports of SOL fees", amount);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    /// Treasury PDA that owns the per-mint fee vaults
+    /// CHECK: PDA used only as the fee vault authority
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Treasury fee vault for the mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        token::mint = mint,
+        token::authority = treasury,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeSellerStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Any seller wallet; only its key seeds the stats account
+    pub seller: UncheckedAccount<'info>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    /// Seller's settled volume in the mint
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + SellerStats::INIT_SPACE,
+        seeds = [SELLER_STATS_SEED, seller.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub seller_stats: Account<'info, SellerStats>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    /// Treasury PDA that owns the per-mint fee vaults
+    /// CHECK: PDA used only as the fee vault authority
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Treasury fee vault for the mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = treasury,
+        seeds = [FEE_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+    
+    /// Per-mint withdrawal accounting
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeLedger::INIT_SPACE,
+        seeds = [FEE_LEDGER_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    
+    /// Fee recipient's token account for the mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = protocol_state.fee_recipient
+    )]
+    pub destination: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSolFees<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [PROTOCOL_SEED],
+        bump = protocol_state.bump
+    )]
+    pub protocol_state: Account<'info, ProtocolState>,
+    
+    /// Role grants (may be omitted when signed by the protocol authority)
+    #[account(
+        seeds = [ROLES_SEED],
+        bump = roles.bump
+    )]
+    pub roles: Option<Account<'info, ProtocolRoles>>,
+    
+    /// Protocol fees collected in native SOL
+    #[account(
+        mut,
+        seeds = [SOL_FEE_VAULT_SEED],
+        bump = sol_fee_vault.bump
+    )]
+    pub sol_fee_vault: Account<'info, FeeLedger>,
+    
+    /// Protocol fee recipient wallet
+    /// CHECK: Address is constrained to the configured fee recipient
+    #[account(
+        mut,
+        address = protocol_state.fee_recipient
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+}
\ No newline at end of file
@@ -1,5 +1,12 @@
+// Anchor's `#[program]` expansion emits the IDL handlers (which still call
+// the deprecated `AccountInfo::realloc` in 0.31) and, with the `cpi`
+// feature, the CPI wrappers (one parameter per instruction argument) at the
+// crate root, where item-level attributes cannot reach them. Handlers that
+// take many arguments carry their own allow.
+#![allow(deprecated)]
+#![cfg_attr(feature = "cpi", allow(clippy::too_many_arguments))]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Mint};
 
 // TODO: Re-enable Arcium imports after fixing version conflicts
 // use arcium_anchor::{
@@ -19,13 +26,12 @@ use anchor_spl::token::{Token, TokenAccount, Mint};
 //     init_computation_definition_accounts, queue_computation_accounts,
 // };
 
-mod instructions;
-mod state;
-mod error;
+pub mod instructions;
+pub mod state;
+pub mod error;
 
 use instructions::*;
 use state::*;
-use error::*;
 
 // Program ID - Generated for Shadow Protocol
 declare_id!("Apw2K9F8KRSgie4iS5ea82Vd3XwTtmojQfXPdbxYFCQm");
@@ -35,6 +41,9 @@ declare_id!("Apw2K9F8KRSgie4iS5ea82Vd3XwTtmojQfXPdbxYFCQm");
 // const COMP_DEF_OFFSET_SEALED_BID: u32 = comp_def_offset("sealed_bid_auction");
 // const COMP_DEF_OFFSET_DUTCH_AUCTION: u32 = comp_def_offset("dutch_auction");
 // const COMP_DEF_OFFSET_BATCH_SETTLEMENT: u32 = comp_def_offset("batch_settlement");
+// const COMP_DEF_OFFSET_MULTI_UNIT: u32 = comp_def_offset("multi_unit_auction");
+// const COMP_DEF_OFFSET_REVERSE_AUCTION: u32 = comp_def_offset("reverse_auction");
+// const COMP_DEF_OFFSET_BATCH_AUCTION: u32 = comp_def_offset("batch_auction");
 
 #[program]
 pub mod shadow_protocol {
@@ -45,14 +54,17 @@ pub mod shadow_protocol {
     // ========================================
 
     /// Initialize a new sealed-bid auction
-    pub fn create_sealed_auction(
-        ctx: Context<CreateSealedAuction>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_sealed_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateSealedAuction<'info>>,
         asset_mint: Pubkey,
         asset_amount: u64,
         duration: u64,
         minimum_bid: u64,
         reserve_price_encrypted: [u8; 32],
         reserve_price_nonce: u128,
+        nft: Option<NftOptions>,
+        timing_mode: TimingMode,
     ) -> Result<()> {
         instructions::create_sealed_auction(
             ctx,
@@ -62,12 +74,15 @@ pub mod shadow_protocol {
             minimum_bid,
             reserve_price_encrypted,
             reserve_price_nonce,
+            nft,
+            timing_mode,
         )
     }
 
     /// Initialize a new Dutch auction with hidden reserve
-    pub fn create_dutch_auction(
-        ctx: Context<CreateDutchAuction>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_dutch_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateDutchAuction<'info>>,
         asset_mint: Pubkey,
         asset_amount: u64,
         starting_price: u64,
@@ -76,6 +91,8 @@ pub mod shadow_protocol {
         duration: u64,
         reserve_price_encrypted: [u8; 32],
         reserve_price_nonce: u128,
+        nft: Option<NftOptions>,
+        timing_mode: TimingMode,
     ) -> Result<()> {
         instructions::create_dutch_auction(
             ctx,
@@ -87,14 +104,118 @@ pub mod shadow_protocol {
             duration,
             reserve_price_encrypted,
             reserve_price_nonce,
+            nft,
+            timing_mode,
+        )
+    }
+
+    /// Initialize a new multi-unit sealed-bid auction with uniform clearing price
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_multi_unit_auction(
+        ctx: Context<CreateMultiUnitAuction>,
+        asset_mint: Pubkey,
+        total_units: u64,
+        unit_size: u64,
+        duration: u64,
+        minimum_bid: u64,
+        reserve_price_encrypted: [u8; 32],
+        reserve_price_nonce: u128,
+    ) -> Result<()> {
+        instructions::create_multi_unit_auction(
+            ctx,
+            asset_mint,
+            total_units,
+            unit_size,
+            duration,
+            minimum_bid,
+            reserve_price_encrypted,
+            reserve_price_nonce,
         )
     }
 
+    /// Initialize a new reverse (procurement) auction where the lowest ask wins
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_reverse_auction(
+        ctx: Context<CreateReverseAuction>,
+        asset_mint: Pubkey,
+        asset_amount: u64,
+        budget: u64,
+        duration: u64,
+        pricing_rule: PricingRule,
+        ceiling_price_encrypted: [u8; 32],
+        ceiling_price_nonce: u128,
+    ) -> Result<()> {
+        instructions::create_reverse_auction(
+            ctx,
+            asset_mint,
+            asset_amount,
+            budget,
+            duration,
+            pricing_rule,
+            ceiling_price_encrypted,
+            ceiling_price_nonce,
+        )
+    }
+
+    /// Initialize a sealed-bid auction selling several SPL assets as one lot
+    pub fn create_bundle_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateBundleAuction<'info>>,
+        amounts: Vec<u64>,
+        duration: u64,
+        minimum_bid: u64,
+        reserve_price_encrypted: [u8; 32],
+        reserve_price_nonce: u128,
+    ) -> Result<()> {
+        instructions::create_bundle_auction(
+            ctx,
+            amounts,
+            duration,
+            minimum_bid,
+            reserve_price_encrypted,
+            reserve_price_nonce,
+        )
+    }
+
+    /// Cancel a bundle auction and return every bundled asset to its creator
+    pub fn cancel_bundle_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelBundleAuction<'info>>,
+        auction_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_bundle_auction(ctx, auction_id)
+    }
+
+    /// Publish title, description and media URI for an auction
+    pub fn create_auction_metadata(
+        ctx: Context<CreateAuctionMetadata>,
+        auction_id: u64,
+        args: AuctionMetadataArgs,
+    ) -> Result<()> {
+        instructions::create_auction_metadata(ctx, auction_id, args)
+    }
+
+    /// Edit an auction's metadata before the first bid
+    pub fn update_auction_metadata(
+        ctx: Context<UpdateAuctionMetadata>,
+        auction_id: u64,
+        args: AuctionMetadataArgs,
+    ) -> Result<()> {
+        instructions::update_auction_metadata(ctx, auction_id, args)
+    }
+
+    /// Cancel an auction and return the vault contents to its creator
+    pub fn cancel_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelAuction<'info>>,
+        auction_id: u64,
+    ) -> Result<()> {
+        instructions::cancel_auction(ctx, auction_id)
+    }
+
     // ========================================
     // Bidding Instructions
     // ========================================
 
     /// Submit an encrypted bid to a sealed auction
+    #[allow(clippy::too_many_arguments)]
     pub fn submit_encrypted_bid(
         ctx: Context<SubmitBid>,
         auction_id: u64,
@@ -102,6 +223,8 @@ pub mod shadow_protocol {
         public_key: [u8; 32],
         nonce: u128,
         collateral_amount: u64,
+        referrer: Option<Pubkey>,
+        allowlist_proof: Vec<[u8; 32]>,
         computation_offset: u64,
     ) -> Result<()> {
         instructions::submit_encrypted_bid(
@@ -111,6 +234,8 @@ pub mod shadow_protocol {
             public_key,
             nonce,
             collateral_amount,
+            referrer,
+            allowlist_proof,
             computation_offset,
         )
     }
@@ -121,14 +246,161 @@ pub mod shadow_protocol {
         auction_id: u64,
         bid_amount: u64,
         collateral_amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::submit_dutch_bid(ctx, auction_id, bid_amount, collateral_amount, allowlist_proof)
+    }
+
+    /// Restrict bidding on an auction to an allowlist, token holders or attested bidders
+    pub fn set_auction_gate(
+        ctx: Context<UpdateBidRules>,
+        auction_id: u64,
+        gate: Option<BidderGate>,
+    ) -> Result<()> {
+        instructions::set_auction_gate(ctx, auction_id, gate)
+    }
+
+    /// Escrow a SOL bounty for the keeper that cranks settlement
+    pub fn fund_keeper_bounty(
+        ctx: Context<FundKeeperBounty>,
+        auction_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_keeper_bounty(ctx, auction_id, amount)
+    }
+
+    /// Denominate an auction's minimum bid, Dutch floor and reserve in USD
+    pub fn set_usd_pricing(
+        ctx: Context<UpdateUsdPricing>,
+        auction_id: u64,
+        pricing: Option<UsdPricing>,
+    ) -> Result<()> {
+        instructions::set_usd_pricing(ctx, auction_id, pricing)
+    }
+
+    /// Set the non-refundable bond bidders pay the seller
+    pub fn set_bid_bond(ctx: Context<UpdateBidRules>, auction_id: u64, bid_bond: u64) -> Result<()> {
+        instructions::set_bid_bond(ctx, auction_id, bid_bond)
+    }
+
+    /// Submit an encrypted (price, quantity) bid to a multi-unit auction
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_multi_unit_bid(
+        ctx: Context<SubmitBid>,
+        auction_id: u64,
+        price_encrypted: [u8; 32],
+        quantity_encrypted: [u8; 32],
+        public_key: [u8; 32],
+        nonce: u128,
+        collateral_amount: u64,
+        computation_offset: u64,
     ) -> Result<()> {
-        instructions::submit_dutch_bid(ctx, auction_id, bid_amount, collateral_amount)
+        instructions::submit_multi_unit_bid(
+            ctx,
+            auction_id,
+            price_encrypted,
+            quantity_encrypted,
+            public_key,
+            nonce,
+            collateral_amount,
+            computation_offset,
+        )
+    }
+
+    /// Submit an encrypted ask to a reverse auction
+    pub fn submit_encrypted_ask(
+        ctx: Context<SubmitBid>,
+        auction_id: u64,
+        ask_amount_encrypted: [u8; 32],
+        public_key: [u8; 32],
+        nonce: u128,
+        computation_offset: u64,
+    ) -> Result<()> {
+        instructions::submit_encrypted_ask(
+            ctx,
+            auction_id,
+            ask_amount_encrypted,
+            public_key,
+            nonce,
+            computation_offset,
+        )
+    }
+
+    /// Reclaim escrowed collateral from a losing, cancelled or Dutch bid
+    pub fn claim_refund(ctx: Context<ClaimRefund>, auction_id: u64) -> Result<()> {
+        instructions::claim_refund(ctx, auction_id)
+    }
+
+    /// Submit an encrypted bid to a sealed auction with native SOL collateral
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_encrypted_bid_sol(
+        ctx: Context<SubmitSolBid>,
+        auction_id: u64,
+        bid_amount_encrypted: [u8; 32],
+        public_key: [u8; 32],
+        nonce: u128,
+        collateral_amount: u64,
+        referrer: Option<Pubkey>,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::submit_encrypted_bid_sol(
+            ctx,
+            auction_id,
+            bid_amount_encrypted,
+            public_key,
+            nonce,
+            collateral_amount,
+            referrer,
+            allowlist_proof,
+        )
+    }
+
+    /// Take a Dutch auction with native SOL collateral
+    pub fn submit_dutch_bid_sol(
+        ctx: Context<SubmitDutchSolBid>,
+        auction_id: u64,
+        bid_amount: u64,
+        collateral_amount: u64,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::submit_dutch_bid_sol(
+            ctx,
+            auction_id,
+            bid_amount,
+            collateral_amount,
+            allowlist_proof,
+        )
+    }
+
+    /// Reclaim native SOL collateral and close its escrow
+    pub fn claim_sol_refund(ctx: Context<ClaimSolRefund>, auction_id: u64) -> Result<()> {
+        instructions::claim_sol_refund(ctx, auction_id)
     }
 
     // ========================================
     // Settlement Instructions
     // ========================================
 
+    /// Pay out an authorized auction and release the asset to the winner
+    pub fn execute_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSettlement<'info>>,
+        auction_id: u64,
+        winner: Pubkey,
+        winning_amount: u64,
+    ) -> Result<()> {
+        instructions::execute_settlement(ctx, auction_id, winner, winning_amount)
+    }
+
+    /// Settle an auction paid in native SOL
+    pub fn execute_sol_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteSolSettlement<'info>>,
+        auction_id: u64,
+        winner: Pubkey,
+        winning_amount: u64,
+    ) -> Result<()> {
+        instructions::execute_sol_settlement(ctx, auction_id, winner, winning_amount)
+    }
+
     /// Authorize settlement after MPC computation verification
     pub fn authorize_settlement(
         ctx: Context<AuthorizeSettlement>,
@@ -148,14 +420,154 @@ pub mod shadow_protocol {
     }
 
     /// Process batch settlement of multiple auctions
-    pub fn batch_settle(
-        ctx: Context<BatchSettle>,
+    pub fn batch_settle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchSettle<'info>>,
         auction_ids: Vec<u64>,
         computation_offset: u64,
     ) -> Result<()> {
         instructions::batch_settle(ctx, auction_ids, computation_offset)
     }
 
+    /// Apply the per-auction results of a batch settlement
+    pub fn apply_batch_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApplyBatchSettlement<'info>>,
+        results: Vec<SingleAuctionResult>,
+        mpc_verification_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::apply_batch_settlement(ctx, results, mpc_verification_hash)
+    }
+
+    /// Close a finished batch settlement to reclaim rent
+    pub fn close_batch(ctx: Context<CloseBatch>, batch_id: u64) -> Result<()> {
+        instructions::close_batch(ctx, batch_id)
+    }
+
+    /// Close a finished auction and its vaults, returning rent to the creator
+    pub fn close_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseAuction<'info>>,
+        auction_id: u64,
+    ) -> Result<()> {
+        instructions::close_auction(ctx, auction_id)
+    }
+
+    /// Close a settled or refunded bid and its escrow
+    pub fn close_bid(ctx: Context<CloseBid>, auction_id: u64) -> Result<()> {
+        instructions::close_bid(ctx, auction_id)
+    }
+
+    /// Close a settled batch auction order
+    pub fn close_batch_order(ctx: Context<CloseBatchOrder>, auction_id: u64) -> Result<()> {
+        instructions::close_batch_order(ctx, auction_id)
+    }
+
+    /// Record the clearing price and unit allocations of a multi-unit auction
+    pub fn authorize_multi_unit_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AuthorizeMultiUnitSettlement<'info>>,
+        auction_id: u64,
+        clearing_price: u64,
+        allocations: Vec<u64>,
+        mpc_verification_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::authorize_multi_unit_settlement(
+            ctx,
+            auction_id,
+            clearing_price,
+            allocations,
+            mpc_verification_hash,
+        )
+    }
+
+    /// Settle a bundle auction, transferring every bundled asset atomically
+    pub fn execute_bundle_settlement<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBundleSettlement<'info>>,
+        auction_id: u64,
+        winning_amount: u64,
+    ) -> Result<()> {
+        instructions::execute_bundle_settlement(ctx, auction_id, winning_amount)
+    }
+
+    /// Record the winning supplier and asks of a reverse auction
+    pub fn authorize_reverse_settlement(
+        ctx: Context<AuthorizeReverseSettlement>,
+        auction_id: u64,
+        winning_ask: u64,
+        runner_up_ask: Option<u64>,
+        mpc_verification_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::authorize_reverse_settlement(
+            ctx,
+            auction_id,
+            winning_ask,
+            runner_up_ask,
+            mpc_verification_hash,
+        )
+    }
+
+    /// Award a reverse auction to the winning supplier
+    pub fn execute_reverse_settlement(ctx: Context<ExecuteReverseSettlement>, auction_id: u64) -> Result<()> {
+        instructions::execute_reverse_settlement(ctx, auction_id)
+    }
+
+    /// Settle a single bid of a cleared multi-unit auction
+    pub fn settle_multi_unit_bid(ctx: Context<SettleMultiUnitBid>, auction_id: u64) -> Result<()> {
+        instructions::settle_multi_unit_bid(ctx, auction_id)
+    }
+
+    // ========================================
+    // Batch Auction Market
+    // ========================================
+
+    /// Open a batch auction epoch for a base/quote token pair
+    pub fn create_batch_auction(
+        ctx: Context<CreateBatchAuction>,
+        base_mint: Pubkey,
+        epoch_duration: u64,
+    ) -> Result<()> {
+        instructions::create_batch_auction(ctx, base_mint, epoch_duration)
+    }
+
+    /// Submit an encrypted limit order to a batch auction epoch
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_batch_order(
+        ctx: Context<SubmitBatchOrder>,
+        auction_id: u64,
+        side_encrypted: [u8; 32],
+        price_encrypted: [u8; 32],
+        size_encrypted: [u8; 32],
+        public_key: [u8; 32],
+        nonce: u128,
+        base_amount: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        instructions::submit_batch_order(
+            ctx,
+            auction_id,
+            side_encrypted,
+            price_encrypted,
+            size_encrypted,
+            public_key,
+            nonce,
+            base_amount,
+            quote_amount,
+        )
+    }
+
+    /// Record the uniform clearing price and order fills of a batch epoch
+    pub fn record_batch_fills<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecordBatchFills<'info>>,
+        auction_id: u64,
+        clearing_price: u64,
+        fills: Vec<BatchFill>,
+        mpc_verification_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::record_batch_fills(ctx, auction_id, clearing_price, fills, mpc_verification_hash)
+    }
+
+    /// Pay out a single order of a cleared batch epoch
+    pub fn settle_batch_order(ctx: Context<SettleBatchOrder>, auction_id: u64) -> Result<()> {
+        instructions::settle_batch_order(ctx, auction_id)
+    }
+
     // ========================================
     // Computation Definition Initialization
     // ========================================
@@ -225,14 +637,129 @@ pub mod shadow_protocol {
         instructions::set_pause_state(ctx, paused)
     }
 
-    /// Update protocol fee (admin only)
-    pub fn update_protocol_fee(ctx: Context<UpdateProtocolFee>, new_fee: u16) -> Result<()> {
-        instructions::update_protocol_fee(ctx, new_fee)
+    /// Pause individual operations or auction types (PAUSE_* bitmask)
+    pub fn set_pause_flags(ctx: Context<SetPauseState>, pause_flags: u16) -> Result<()> {
+        instructions::set_pause_flags(ctx, pause_flags)
+    }
+
+    /// Freeze or unfreeze a single auction
+    pub fn set_auction_frozen(ctx: Context<SetAuctionFrozen>, auction_id: u64, frozen: bool) -> Result<()> {
+        instructions::set_auction_frozen(ctx, auction_id, frozen)
+    }
+
+    /// Queue a protocol fee change behind the fee update timelock
+    pub fn queue_protocol_fee_update(ctx: Context<QueueProtocolFeeUpdate>, new_fee: u16) -> Result<()> {
+        instructions::queue_protocol_fee_update(ctx, new_fee)
+    }
+
+    /// Queue a fee recipient change behind the fee update timelock
+    pub fn queue_fee_recipient_update(ctx: Context<QueueFeeRecipientUpdate>, new_recipient: Pubkey) -> Result<()> {
+        instructions::queue_fee_recipient_update(ctx, new_recipient)
+    }
+
+    /// Apply queued fee changes once their timelock has elapsed
+    pub fn apply_fee_update(ctx: Context<ApplyFeeUpdate>) -> Result<()> {
+        instructions::apply_fee_update(ctx)
+    }
+
+    /// Cancel queued fee changes
+    pub fn cancel_fee_update(ctx: Context<CancelFeeUpdate>) -> Result<()> {
+        instructions::cancel_fee_update(ctx)
+    }
+
+    /// Change the delay applied to future fee updates (admin only)
+    pub fn set_fee_update_delay(ctx: Context<SetFeeUpdateDelay>, new_delay: i64) -> Result<()> {
+        instructions::set_fee_update_delay(ctx, new_delay)
+    }
+
+    /// Publish a price on a mock oracle feed for local testing
+    #[cfg(feature = "mock-oracle")]
+    pub fn set_mock_price(
+        ctx: Context<SetMockPrice>,
+        feed_id: [u8; 32],
+        price: i64,
+        conf: u64,
+        exponent: i32,
+    ) -> Result<()> {
+        instructions::set_mock_price(ctx, feed_id, price, conf, exponent)
+    }
+
+    /// Set the anti-spam bid deposit and per-wallet open bid cap
+    pub fn set_bid_limits(
+        ctx: Context<SetBidLimits>,
+        bid_deposit_lamports: u64,
+        max_active_bids_per_wallet: u32,
+    ) -> Result<()> {
+        instructions::set_bid_limits(ctx, bid_deposit_lamports, max_active_bids_per_wallet)
+    }
+
+    /// Queue a change to the referral fee paid to frontends that originate bids
+    pub fn queue_referral_fee_update(ctx: Context<UpdateFeeConfig>, referral_fee_bps: u16) -> Result<()> {
+        instructions::queue_referral_fee_update(ctx, referral_fee_bps)
+    }
+
+    /// Queue a replacement of the volume-based protocol fee tiers
+    pub fn queue_fee_tiers_update(ctx: Context<UpdateFeeConfig>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::queue_fee_tiers_update(ctx, fee_tiers)
+    }
+
+    /// Create the treasury fee vault for a mint
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        instructions::initialize_fee_vault(ctx)
+    }
+
+    /// Create a seller's volume stats for a payment mint
+    pub fn initialize_seller_stats(ctx: Context<InitializeSellerStats>) -> Result<()> {
+        instructions::initialize_seller_stats(ctx)
+    }
+
+    /// Withdraw collected fees for one mint to the fee recipient
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_fees(ctx, amount)
+    }
+
+    /// Withdraw protocol fees collected in native SOL to the fee recipient
+    pub fn withdraw_sol_fees(ctx: Context<WithdrawSolFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_sol_fees(ctx, amount)
+    }
+
+    /// Create the roles account for delegated admin roles
+    pub fn initialize_roles(ctx: Context<InitializeRoles>) -> Result<()> {
+        instructions::initialize_roles(ctx)
+    }
+
+    /// Grant an admin role to a key
+    pub fn grant_role(ctx: Context<ManageRole>, role: Role, member: Pubkey) -> Result<()> {
+        instructions::grant_role(ctx, role, member)
+    }
+
+    /// Revoke an admin role from a key
+    pub fn revoke_role(ctx: Context<ManageRole>, role: Role, member: Pubkey) -> Result<()> {
+        instructions::revoke_role(ctx, role, member)
     }
 
-    /// Update fee recipient (admin only)
-    pub fn update_fee_recipient(ctx: Context<UpdateFeeRecipient>, new_recipient: Pubkey) -> Result<()> {
-        instructions::update_fee_recipient(ctx, new_recipient)
+    /// Require M-of-N multisig approval for sensitive admin actions
+    pub fn configure_multisig(
+        ctx: Context<ConfigureMultisig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::configure_multisig(ctx, members, threshold)
+    }
+
+    /// Propose a multisig-controlled admin action
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: AdminAction) -> Result<()> {
+        instructions::create_proposal(ctx, action)
+    }
+
+    /// Approve a pending admin proposal
+    pub fn approve_proposal(ctx: Context<ApproveProposal>, proposal_id: u64) -> Result<()> {
+        instructions::approve_proposal(ctx, proposal_id)
+    }
+
+    /// Execute an admin proposal that reached its threshold
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_id: u64) -> Result<()> {
+        instructions::execute_proposal(ctx, proposal_id)
     }
 
     /// Initiate protocol authority transfer (admin only, first step)
@@ -240,9 +767,9 @@ pub mod shadow_protocol {
         instructions::initiate_authority_transfer(ctx, new_authority)
     }
 
-    /// Complete protocol authority transfer (admin only, second step after timelock)
-    pub fn complete_authority_transfer(ctx: Context<CompleteAuthorityTransfer>) -> Result<()> {
-        instructions::complete_authority_transfer(ctx)
+    /// Accept protocol authority transfer (pending authority, second step after timelock)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority(ctx)
     }
 
     /// Cancel pending authority transfer (admin only)
@@ -250,9 +777,28 @@ pub mod shadow_protocol {
         instructions::cancel_authority_transfer(ctx)
     }
 
-    /// Transfer protocol authority (admin only) - DEPRECATED: Use initiate/complete pattern instead
-    pub fn transfer_authority(ctx: Context<TransferAuthority>, new_authority: Pubkey) -> Result<()> {
-        instructions::transfer_authority(ctx, new_authority)
+    // ========================================
+    // Account Migration
+    // ========================================
+
+    /// Upgrade the protocol state to the current layout
+    pub fn migrate_protocol_state(ctx: Context<MigrateProtocolState>) -> Result<()> {
+        instructions::migrate_protocol_state(ctx)
+    }
+
+    /// Upgrade an auction account to the current layout
+    pub fn migrate_auction(ctx: Context<MigrateAuction>, auction_id: u64) -> Result<()> {
+        instructions::migrate_auction(ctx, auction_id)
+    }
+
+    /// Upgrade a bid account to the current layout
+    pub fn migrate_bid(ctx: Context<MigrateBid>, auction_id: u64, bidder: Pubkey) -> Result<()> {
+        instructions::migrate_bid(ctx, auction_id, bidder)
+    }
+
+    /// Upgrade a batch settlement account to the current layout
+    pub fn migrate_batch(ctx: Context<MigrateBatch>, batch_id: u64) -> Result<()> {
+        instructions::migrate_batch(ctx, batch_id)
     }
 }
 
@@ -1,13 +1,54 @@
-use anchor_lang::prelude::*;
-
 // Re-export from mod.rs for consistency
 pub use super::{
     AuctionAccount, 
+    AuctionMetadata,
+    BundleManifest,
+    BundleItem,
     AuctionType, 
     AuctionStatus,
+    TimingMode,
+    PricingRule,
+    BidderGate,
+    NftListing,
+    NftOptions,
+    UsdPricing,
+    SettlementSplits,
     AuctionCreated,
+    NftAuctionCreated,
     AuctionSettled,
+    AuctionCancelled,
+    AuctionClosed,
+    AuctionMetadataUpdated,
+    AuctionGateUpdated,
+    UsdPricingUpdated,
+    KeeperBountyFunded,
+    KeeperRewarded,
+    AuctionFreezeUpdated,
+    MultiUnitAuctionCleared,
+    BatchAuctionCleared,
     AUCTION_SEED,
+    AUCTION_METADATA_SEED,
+    AUCTION_ACCOUNT_VERSION,
     ASSET_VAULT_SEED,
+    BUNDLE_SEED,
+    QUOTE_VAULT_SEED,
     MAX_AUCTION_DURATION,
-};
\ No newline at end of file
+    MAX_AUCTION_DURATION_SLOTS,
+    MAX_TITLE_LEN,
+    MAX_DESCRIPTION_LEN,
+    MAX_CATEGORY_LEN,
+    MAX_URI_LEN,
+    MAX_ALLOWLIST_PROOF_LEN,
+    MAX_BUNDLE_ITEMS,
+    MAX_ORDERS_PER_BATCH,
+    MAX_UNITS_PER_AUCTION,
+    MAX_METADATA_CREATORS,
+    TOKEN_METADATA_PROGRAM_ID,
+    PYTH_RECEIVER_PROGRAM_ID,
+    MAX_PRICE_AGE,
+    MAX_PRICE_CONFIDENCE_BPS,
+    USD_DECIMALS,
+};
+
+#[cfg(feature = "mock-oracle")]
+pub use super::{MockPriceFeed, MOCK_PRICE_FEED_SEED};
\ No newline at end of file
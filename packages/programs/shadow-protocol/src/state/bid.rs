@@ -1,9 +1,26 @@
-use anchor_lang::prelude::*;
-
 // Re-export from mod.rs for consistency
 pub use super::{
     BidAccount,
+    BidderStats,
+    SolEscrow,
+    BatchOrder,
+    BatchFill,
+    OrderSide,
     BidSubmitted,
+    BidSettled,
+    BidRefunded,
+    BidClosed,
+    BidBondUpdated,
+    BatchOrderSubmitted,
+    BatchOrderSettled,
     BID_SEED,
+    BID_ACCOUNT_VERSION,
+    BID_ESCROW_SEED,
+    SOL_ESCROW_SEED,
+    ORDER_SEED,
     MAX_BIDS_PER_AUCTION,
+    BIDDER_STATS_SEED,
+    DEFAULT_BID_DEPOSIT_LAMPORTS,
+    MAX_BID_DEPOSIT_LAMPORTS,
+    DEFAULT_MAX_ACTIVE_BIDS_PER_WALLET,
 };
\ No newline at end of file
@@ -4,10 +4,6 @@ pub mod auction;
 pub mod bid;
 pub mod protocol;
 
-pub use auction::*;
-pub use bid::*;
-pub use protocol::*;
-
 // ========================================
 // Core State Structures
 // ========================================
@@ -19,9 +15,9 @@ pub struct ProtocolState {
     pub authority: Pubkey,
     /// Protocol fee in basis points (e.g., 50 = 0.5%)
     pub protocol_fee: u16,
-    /// Fee recipient
+    /// Wallet that receives fee withdrawals from the treasury
     pub fee_recipient: Pubkey,
-    /// Whether the protocol is paused
+    /// Whether any operation is paused (mirrors pause_flags != 0)
     pub paused: bool,
     /// Next auction ID (auto-incrementing)
     pub next_auction_id: u64,
@@ -33,6 +29,36 @@ pub struct ProtocolState {
     pub bump: u8,
     /// Reserved space for future upgrades
     pub reserved: [u8; 100],
+    /// Next batch settlement ID (auto-incrementing)
+    pub next_batch_id: u64,
+    /// Multisig that must approve sensitive admin actions (None = single authority)
+    pub admin_multisig: Option<Pubkey>,
+    /// Queued protocol fee change
+    pub pending_protocol_fee: Option<u16>,
+    /// Queued fee recipient change
+    pub pending_fee_recipient: Option<Pubkey>,
+    /// When queued fee changes may be applied (timestamp)
+    pub fee_update_timelock: Option<i64>,
+    /// Delay between queuing and applying fee changes (seconds)
+    pub fee_update_delay: i64,
+    /// Paused operations and auction types (PAUSE_* bitmask)
+    pub pause_flags: u16,
+    /// Referral fee paid to the frontend that originated a bid (basis points)
+    pub referral_fee_bps: u16,
+    /// Discounted protocol fees for high-volume sellers, ascending by volume
+    #[max_len(4)]
+    pub fee_tiers: Vec<FeeTier>,
+    /// Layout version (see PROTOCOL_STATE_VERSION)
+    pub version: u8,
+    /// Refundable SOL deposit held on each new bid account
+    pub bid_deposit_lamports: u64,
+    /// Maximum open bids per wallet across auctions (0 = unlimited)
+    pub max_active_bids_per_wallet: u32,
+    /// Queued referral fee change
+    pub pending_referral_fee_bps: Option<u16>,
+    /// Queued fee tier replacement
+    #[max_len(4)]
+    pub pending_fee_tiers: Option<Vec<FeeTier>>,
 }
 
 #[account]
@@ -44,7 +70,8 @@ pub struct AuctionAccount {
     pub creator: Pubkey,
     /// Asset being auctioned
     pub asset_mint: Pubkey,
-    /// Asset vault holding the auctioned item
+    /// Vault holding the auctioned item (the buyer's budget vault for
+    /// reverse auctions)
     pub asset_vault: Pubkey,
     /// Asset amount being auctioned
     pub asset_amount: u64,
@@ -70,7 +97,8 @@ pub struct AuctionAccount {
     pub price_decrease_rate: u64,
     /// Number of bids received
     pub bid_count: u64,
-    /// Winner of the auction (if settled)
+    /// Winner of the auction (if settled, or once a reverse auction's
+    /// result is recorded)
     pub winner: Option<Pubkey>,
     /// Winning bid amount
     pub winning_amount: u64,
@@ -84,6 +112,57 @@ pub struct AuctionAccount {
     pub bump: u8,
     /// Reserved space for future upgrades
     pub reserved: [u8; 32],
+    /// Number of units offered (multi-unit auctions, 1 otherwise)
+    pub total_units: u64,
+    /// Units allocated to winning bids by the clearing computation
+    pub units_allocated: u64,
+    /// Uniform per-unit clearing price (multi-unit auctions)
+    pub clearing_price: u64,
+    /// Number of bids whose allocation has been settled
+    pub bids_settled: u64,
+    /// Payment mint all bids must use (default pubkey if unrestricted)
+    pub payment_mint: Pubkey,
+    /// Pricing rule applied to the winning bid
+    pub pricing_rule: PricingRule,
+    /// Budget escrowed by the buyer (reverse auctions)
+    pub budget: u64,
+    /// Bundle manifest listing the lot's assets (bundle auctions)
+    pub bundle: Option<Pubkey>,
+    /// Batch settlement this auction is committed to
+    pub batch: Option<Pubkey>,
+    /// Protocol fee (basis points) snapshotted at creation
+    pub protocol_fee_bps: u16,
+    /// Referral fee (basis points) snapshotted at creation
+    pub referral_fee_bps: u16,
+    /// Whether bidding and settlement are frozen for this auction
+    pub frozen: bool,
+    /// Bid and order accounts closed after the auction finished
+    pub bids_closed: u64,
+    /// Layout version (see AUCTION_ACCOUNT_VERSION)
+    pub version: u8,
+    /// Restriction on who may bid (None = open auction)
+    pub gate: Option<BidderGate>,
+    /// Non-refundable bond each bidder pays the seller, in the mint bids
+    /// escrow (lamports for SOL auctions)
+    pub bid_bond: u64,
+    /// NFT listing details (None for fungible assets)
+    pub nft: Option<NftListing>,
+    /// USD-denominated price limits (None = fixed token amounts only)
+    pub usd_pricing: Option<UsdPricing>,
+    /// Clock for start_time, end_time and Dutch decay (seconds or slots)
+    pub timing_mode: TimingMode,
+    /// Seller-prepaid SOL reward for the keeper that cranks settlement,
+    /// held as extra lamports on this account
+    pub keeper_bounty: u64,
+    /// Keeper that collected the bounty
+    pub keeper: Option<Pubkey>,
+    /// Bids with an allocation recorded by the clearing computation
+    /// (multi-unit auctions); settlement waits until this reaches bid_count
+    pub allocations_recorded: u64,
+    /// Creator royalty (basis points) snapshotted when the NFT was listed.
+    /// None for fungible assets and for NFT listings made before v9, whose
+    /// royalty is read from metadata at settlement.
+    pub nft_royalty_bps: Option<u16>,
 }
 
 #[account]
@@ -111,6 +190,124 @@ pub struct BidAccount {
     pub bump: u8,
     /// Reserved space
     pub reserved: [u8; 16],
+    /// Encrypted requested quantity (multi-unit auctions)
+    pub quantity_encrypted: [u8; 32],
+    /// Units allocated to this bid by the clearing computation
+    pub units_allocated: u64,
+    /// Whether the allocation, payment and refund have been settled
+    pub settled: bool,
+    /// Frontend that originated the bid and earns the referral fee
+    pub referrer: Option<Pubkey>,
+    /// Layout version (see BID_ACCOUNT_VERSION)
+    pub version: u8,
+    /// Anti-spam deposit (lamports) held on this account until refund
+    pub deposit_lamports: u64,
+    /// Whether the clearing computation has recorded this bid's allocation
+    pub allocation_recorded: bool,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BatchOrder {
+    /// Batch auction epoch this order belongs to
+    pub auction_id: u64,
+    /// Trader placing the order
+    pub trader: Pubkey,
+    /// Encrypted order side (buy or sell)
+    pub side_encrypted: [u8; 32],
+    /// Encrypted limit price (quote units per base unit)
+    pub price_encrypted: [u8; 32],
+    /// Encrypted order size (base units)
+    pub size_encrypted: [u8; 32],
+    /// Public key for encryption
+    pub encryption_public_key: [u8; 32],
+    /// Encryption nonce
+    pub nonce: u128,
+    /// Order timestamp
+    pub timestamp: i64,
+    /// Base tokens deposited into the epoch vault
+    pub base_deposited: u64,
+    /// Quote tokens deposited into the epoch vault
+    pub quote_deposited: u64,
+    /// Fill computed at epoch clearing (None if unfilled)
+    pub fill: Option<BatchFill>,
+    /// Whether fills and refunds have been paid out
+    pub settled: bool,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolRoles {
+    /// Role grants issued by the protocol authority
+    #[max_len(16)]
+    pub grants: Vec<RoleGrant>,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct FeeLedger {
+    /// Mint of the fee vault this ledger tracks
+    pub mint: Pubkey,
+    /// Total fees withdrawn from the vault
+    pub total_withdrawn: u64,
+    /// Number of withdrawals made
+    pub withdrawal_count: u64,
+    /// Last withdrawal timestamp
+    pub last_withdrawal_at: Option<i64>,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AdminMultisig {
+    /// Keys allowed to propose and approve admin actions
+    #[max_len(10)]
+    pub members: Vec<Pubkey>,
+    /// Approvals required to execute a proposal
+    pub threshold: u8,
+    /// Next proposal ID (auto-incrementing)
+    pub next_proposal_id: u64,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AdminProposal {
+    /// Unique proposal identifier
+    pub proposal_id: u64,
+    /// Member who created the proposal
+    pub proposer: Pubkey,
+    /// Admin action to execute
+    pub action: AdminAction,
+    /// Members who have approved
+    #[max_len(10)]
+    pub approvals: Vec<Pubkey>,
+    /// Whether the action has been executed
+    pub executed: bool,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Bump seed
+    pub bump: u8,
+    /// Timestamp after which the proposal can no longer be approved or executed
+    pub expires_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BundleManifest {
+    /// Auction this bundle is sold in
+    pub auction_id: u64,
+    /// Assets making up the lot
+    #[max_len(8)]
+    pub items: Vec<BundleItem>,
+    /// Bump seed
+    pub bump: u8,
 }
 
 #[account]
@@ -133,6 +330,151 @@ pub struct BatchSettlement {
     pub bump: u8,
     /// Reserved space
     pub reserved: [u8; 64],
+    /// Auctions whose result could not be applied
+    #[max_len(10)]
+    pub failed_auction_ids: Vec<u64>,
+    /// Layout version (see BATCH_SETTLEMENT_VERSION)
+    pub version: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct BundleItem {
+    /// Mint of the bundled asset
+    pub mint: Pubkey,
+    /// Amount of the asset in the lot
+    pub amount: u64,
+    /// Auction-owned vault holding the asset
+    pub vault: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct RoleGrant {
+    /// Role held
+    pub role: Role,
+    /// Key holding the role
+    pub member: Pubkey,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionMetadata {
+    /// Auction this metadata describes
+    pub auction_id: u64,
+    /// Auction creator (only signer allowed to edit)
+    pub creator: Pubkey,
+    /// Listing title
+    #[max_len(64)]
+    pub title: String,
+    /// Listing description
+    #[max_len(512)]
+    pub description: String,
+    /// Listing category (free-form, e.g. "art" or "treasury-bills")
+    #[max_len(32)]
+    pub category: String,
+    /// Off-chain URI with media and extended details
+    #[max_len(200)]
+    pub uri: String,
+    /// Hash of the content at `uri` so indexers can detect tampering
+    pub content_hash: [u8; 32],
+    /// Last update timestamp
+    pub updated_at: i64,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[cfg(feature = "mock-oracle")]
+#[account]
+#[derive(InitSpace)]
+pub struct MockPriceFeed {
+    /// Feed id, matched like a real oracle feed id
+    pub feed_id: [u8; 32],
+    /// Price in units of 10^exponent USD
+    pub price: i64,
+    /// Confidence interval, same units as `price`
+    pub conf: u64,
+    /// Price exponent (e.g. -8)
+    pub exponent: i32,
+    /// Publish timestamp
+    pub publish_time: i64,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SolEscrow {
+    /// Auction the collateral is escrowed for
+    pub auction_id: u64,
+    /// Bidder who deposited the collateral
+    pub bidder: Pubkey,
+    /// Collateral held above the account's rent-exempt balance
+    pub amount: u64,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct BidderStats {
+    /// Bidder these statistics belong to
+    pub bidder: Pubkey,
+    /// Bid accounts opened and not yet closed
+    pub active_bids: u32,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SellerStats {
+    /// Seller these statistics belong to
+    pub seller: Pubkey,
+    /// Total settled sale volume (payment token base units)
+    pub total_volume: u64,
+    /// Number of settled sales
+    pub settled_count: u64,
+    /// Bump seed
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct FeeTier {
+    /// Seller volume required to qualify for the tier
+    pub min_volume: u64,
+    /// Protocol fee charged in the tier (basis points)
+    pub fee_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SettlementSplits {
+    /// Fee paid to the protocol treasury
+    pub protocol_fee: u64,
+    /// Fee paid to the bid's referrer
+    pub referral_fee: u64,
+    /// Royalties paid to the asset's creators
+    pub royalty_fee: u64,
+    /// Proceeds paid to the seller
+    pub seller_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SingleAuctionResult {
+    /// Auction this result applies to
+    pub auction_id: u64,
+    /// Winning bidder (None if no eligible bid)
+    pub winner: Option<Pubkey>,
+    /// Price the winner pays
+    pub winning_amount: u64,
+    /// Whether the winning bid met the encrypted reserve
+    pub reserve_met: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct BatchFill {
+    /// Side the order was filled on
+    pub side: OrderSide,
+    /// Base units bought or sold at the clearing price
+    pub quantity: u64,
 }
 
 // ========================================
@@ -144,6 +486,72 @@ pub enum AuctionType {
     SealedBid,
     Dutch,
     Batch,
+    MultiUnit,
+    Reverse,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PricingRule {
+    /// Winner pays (or is paid) its own bid
+    FirstPrice,
+    /// Winner pays (or is paid) the runner-up bid
+    SecondPrice,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum BidderGate {
+    /// Bidder proves membership in a Merkle tree of allowed wallets
+    Merkle { root: [u8; 32] },
+    /// Bidder holds at least `min_amount` of `mint` (e.g. a KYC or pass NFT)
+    TokenHolding { mint: Pubkey, min_amount: u64 },
+    /// A seller-designated attester co-signs each bid transaction
+    Attester { attester: Pubkey },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct NftListing {
+    /// Verified Metaplex collection the NFT was required to belong to
+    pub collection: Option<Pubkey>,
+    /// Programmable NFT, moved through the token-metadata transfer path
+    pub programmable: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct UsdPricing {
+    /// Oracle price account quoting the payment token in USD
+    pub price_feed: Pubkey,
+    /// Feed id the price account must carry
+    pub feed_id: [u8; 32],
+    /// Decimals of the payment token
+    pub payment_decimals: u8,
+    /// Minimum sealed bid in micro-USD (0 = none)
+    pub minimum_bid_usd: u64,
+    /// Dutch price floor in micro-USD (0 = none)
+    pub minimum_price_floor_usd: u64,
+    /// Public reserve in micro-USD, enforced at settlement (0 = none)
+    pub reserve_usd: u64,
+}
+
+/// Seller options for listing an NFT
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NftOptions {
+    /// Reject the listing unless the NFT is a verified member of this collection
+    pub required_collection: Option<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug, Default)]
+pub enum TimingMode {
+    /// Times are unix timestamps; Dutch decay is per second
+    #[default]
+    Timestamp,
+    /// Times are slots; Dutch decay is per slot
+    Slot,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
@@ -155,6 +563,64 @@ pub enum AuctionStatus {
     Cancelled,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum Role {
+    /// May pause and unpause the protocol
+    Pauser,
+    /// May change the protocol fee and fee recipient
+    FeeManager,
+    /// May attest MPC settlement results
+    SettlementAttester,
+    /// May coordinate program upgrades and account migrations
+    UpgradeAuthority,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum AdminAction {
+    /// Queue a protocol fee change (basis points)
+    SetProtocolFee { new_fee: u16 },
+    /// Queue a fee recipient change
+    SetFeeRecipient { new_recipient: Pubkey },
+    /// Start the timelocked authority transfer
+    TransferAuthority { new_authority: Pubkey },
+    /// Resume a paused protocol
+    Unpause,
+    /// Lift the freeze on a single auction
+    UnfreezeAuction { auction_id: u64 },
+    /// Queue a referral fee change (basis points)
+    SetReferralFee { referral_fee_bps: u16 },
+    /// Queue a replacement of the volume-based fee tiers
+    SetFeeTiers {
+        #[max_len(4)]
+        fee_tiers: Vec<FeeTier>,
+    },
+    /// Add a multisig member
+    AddMember { member: Pubkey },
+    /// Remove a multisig member
+    RemoveMember { member: Pubkey },
+    /// Change the approvals required to execute a proposal
+    ChangeThreshold { threshold: u8 },
+    /// Change the delay applied to future fee updates (seconds)
+    SetFeeUpdateDelay { new_delay: i64 },
+    /// Set the anti-spam bid deposit and per-wallet open bid cap
+    SetBidLimits {
+        bid_deposit_lamports: u64,
+        max_active_bids_per_wallet: u32,
+    },
+    /// Grant a delegated admin role, creating the roles account if needed
+    GrantRole { role: Role, member: Pubkey },
+    /// Revoke a delegated admin role
+    RevokeRole { role: Role, member: Pubkey },
+    /// Publish a price on an existing mock price feed
+    #[cfg(feature = "mock-oracle")]
+    SetMockPrice {
+        feed_id: [u8; 32],
+        price: i64,
+        conf: u64,
+        exponent: i32,
+    },
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum BatchStatus {
     Created,
@@ -176,6 +642,7 @@ pub struct AuctionCreated {
     pub start_time: i64,
     pub end_time: i64,
     pub minimum_bid: u64,
+    pub timing_mode: TimingMode,
 }
 
 #[event]
@@ -191,9 +658,132 @@ pub struct AuctionSettled {
     pub auction_id: u64,
     pub winner: Option<Pubkey>,
     pub winning_amount: u64,
+    /// Payout breakdown (None when payouts happen per bid or later)
+    pub splits: Option<SettlementSplits>,
     pub settled_at: i64,
 }
 
+#[event]
+pub struct AuctionGateUpdated {
+    pub auction_id: u64,
+    pub gate: Option<BidderGate>,
+}
+
+#[event]
+pub struct NftAuctionCreated {
+    pub auction_id: u64,
+    pub mint: Pubkey,
+    pub collection: Option<Pubkey>,
+    pub programmable: bool,
+}
+
+#[event]
+pub struct KeeperBountyFunded {
+    pub auction_id: u64,
+    pub amount: u64,
+    pub total_bounty: u64,
+}
+
+#[event]
+pub struct KeeperRewarded {
+    pub auction_id: u64,
+    pub keeper: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct UsdPricingUpdated {
+    pub auction_id: u64,
+    pub pricing: Option<UsdPricing>,
+}
+
+#[event]
+pub struct AuctionMetadataUpdated {
+    pub auction_id: u64,
+    pub uri: String,
+    pub content_hash: [u8; 32],
+}
+
+#[event]
+pub struct BidBondUpdated {
+    pub auction_id: u64,
+    pub bid_bond: u64,
+}
+
+#[event]
+pub struct BidLimitsUpdated {
+    pub bid_deposit_lamports: u64,
+    pub max_active_bids_per_wallet: u32,
+}
+
+#[event]
+pub struct AuctionClosed {
+    pub auction_id: u64,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct BidClosed {
+    pub auction_id: u64,
+    pub bidder: Pubkey,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub auction_id: u64,
+    pub cancelled_by: Pubkey,
+    pub cancelled_at: i64,
+}
+
+#[event]
+pub struct BidRefunded {
+    pub auction_id: u64,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct MultiUnitAuctionCleared {
+    pub auction_id: u64,
+    pub clearing_price: u64,
+    pub units_allocated: u64,
+    pub total_units: u64,
+}
+
+#[event]
+pub struct BidSettled {
+    pub auction_id: u64,
+    pub bidder: Pubkey,
+    pub units: u64,
+    pub payment: u64,
+    pub refund: u64,
+}
+
+#[event]
+pub struct BatchOrderSubmitted {
+    pub auction_id: u64,
+    pub trader: Pubkey,
+    pub timestamp: i64,
+    pub order_count: u64,
+}
+
+#[event]
+pub struct BatchAuctionCleared {
+    pub auction_id: u64,
+    pub clearing_price: u64,
+    pub matched_volume: u64,
+    pub filled_orders: u64,
+}
+
+#[event]
+pub struct BatchOrderSettled {
+    pub auction_id: u64,
+    pub trader: Pubkey,
+    pub fill: Option<BatchFill>,
+    pub base_out: u64,
+    pub quote_out: u64,
+}
+
 #[event]
 pub struct BatchSettlementCreated {
     pub batch_id: u64,
@@ -208,6 +798,134 @@ pub struct BatchSettled {
     pub settled_at: i64,
 }
 
+#[event]
+pub struct BatchAuctionFailed {
+    pub batch_id: u64,
+    pub auction_id: u64,
+}
+
+#[event]
+pub struct AuthorityTransferInitiated {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timelock_expires: i64,
+}
+
+#[event]
+pub struct AuthorityTransferCompleted {
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub pending_authority: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub previous_flags: u16,
+    pub pause_flags: u16,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct AuctionFreezeUpdated {
+    pub auction_id: u64,
+    pub frozen: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct FeeUpdateQueued {
+    pub pending_protocol_fee: Option<u16>,
+    pub pending_fee_recipient: Option<Pubkey>,
+    pub pending_referral_fee_bps: Option<u16>,
+    pub pending_fee_tiers: Option<Vec<FeeTier>>,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct FeeUpdateApplied {
+    pub protocol_fee: u16,
+    pub fee_recipient: Pubkey,
+    pub referral_fee_bps: u16,
+    pub fee_tiers: Vec<FeeTier>,
+}
+
+#[event]
+pub struct FeeUpdateCancelled {
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct ProtocolFeeCollected {
+    pub auction_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FeesWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub total_withdrawn: u64,
+    pub remaining_balance: u64,
+    pub withdrawn_by: Pubkey,
+}
+
+#[event]
+pub struct MultisigConfigured {
+    pub multisig: Pubkey,
+    pub member_count: u8,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal_id: u64,
+    pub approver: Pubkey,
+    pub approvals: u8,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub action: AdminAction,
+    pub executed_by: Pubkey,
+}
+
+#[event]
+pub struct RoleGranted {
+    pub role: Role,
+    pub member: Pubkey,
+    pub granted_by: Pubkey,
+}
+
+#[event]
+pub struct RoleRevoked {
+    pub role: Role,
+    pub member: Pubkey,
+    pub revoked_by: Pubkey,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub migrated_by: Pubkey,
+}
+
 // ========================================
 // Constants
 // ========================================
@@ -215,20 +933,143 @@ pub struct BatchSettled {
 pub const PROTOCOL_SEED: &[u8] = b"protocol";
 pub const AUCTION_SEED: &[u8] = b"auction";
 pub const BID_SEED: &[u8] = b"bid";
+pub const BID_ESCROW_SEED: &[u8] = b"bid_escrow";
 pub const ASSET_VAULT_SEED: &[u8] = b"asset_vault";
+pub const BUDGET_VAULT_SEED: &[u8] = b"budget_vault";
 pub const BATCH_SEED: &[u8] = b"batch";
+pub const BUNDLE_SEED: &[u8] = b"bundle";
+pub const ORDER_SEED: &[u8] = b"order";
+pub const QUOTE_VAULT_SEED: &[u8] = b"quote_vault";
+pub const ROLES_SEED: &[u8] = b"roles";
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const FEE_LEDGER_SEED: &[u8] = b"fee_ledger";
+pub const SELLER_STATS_SEED: &[u8] = b"seller_stats";
+pub const BIDDER_STATS_SEED: &[u8] = b"bidder_stats";
+pub const AUCTION_METADATA_SEED: &[u8] = b"auction_metadata";
+pub const SOL_ESCROW_SEED: &[u8] = b"sol_escrow";
+pub const SOL_FEE_VAULT_SEED: &[u8] = b"sol_fee_vault";
+#[cfg(feature = "mock-oracle")]
+pub const MOCK_PRICE_FEED_SEED: &[u8] = b"mock_price_feed";
+
+// Pause flags for protocol operations
+pub const PAUSE_CREATE: u16 = 1 << 0;
+pub const PAUSE_BID: u16 = 1 << 1;
+pub const PAUSE_SETTLE: u16 = 1 << 2;
+
+// Pause flags for individual auction types
+pub const PAUSE_SEALED_BID: u16 = 1 << 8;
+pub const PAUSE_DUTCH: u16 = 1 << 9;
+pub const PAUSE_BATCH: u16 = 1 << 10;
+pub const PAUSE_MULTI_UNIT: u16 = 1 << 11;
+pub const PAUSE_REVERSE: u16 = 1 << 12;
+
+// Every pausable operation and auction type
+pub const PAUSE_ALL: u16 = PAUSE_CREATE
+    | PAUSE_BID
+    | PAUSE_SETTLE
+    | PAUSE_SEALED_BID
+    | PAUSE_DUTCH
+    | PAUSE_BATCH
+    | PAUSE_MULTI_UNIT
+    | PAUSE_REVERSE;
+
+// Current account layout versions. Every field added since the original
+// (unversioned) layout is appended after `reserved`, so pre-versioning
+// accounts decode with `version == 0` once grown and zero-filled. New fields
+// must decode from zeroed bytes; bump the version when adding one and the
+// matching `migrate_*` instruction will grow old accounts in place.
+pub const PROTOCOL_STATE_VERSION: u8 = 3;
+pub const AUCTION_ACCOUNT_VERSION: u8 = 9;
+pub const BID_ACCOUNT_VERSION: u8 = 3;
+pub const BATCH_SETTLEMENT_VERSION: u8 = 1;
+
+// Auction metadata field limits (bytes)
+pub const MAX_TITLE_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 512;
+pub const MAX_CATEGORY_LEN: usize = 32;
+pub const MAX_URI_LEN: usize = 200;
+
+// Maximum Merkle proof length for allowlist gates
+pub const MAX_ALLOWLIST_PROOF_LEN: usize = 24;
 
 // Maximum auction duration (30 days)
 pub const MAX_AUCTION_DURATION: i64 = 30 * 24 * 60 * 60;
 
+// Maximum auction duration in slots (30 days of 400ms slots)
+pub const MAX_AUCTION_DURATION_SLOTS: i64 = MAX_AUCTION_DURATION * 5 / 2;
+
 // Maximum protocol fee (5%)
 pub const MAX_PROTOCOL_FEE: u16 = 500;
 
+// Maximum referral fee (1%)
+pub const MAX_REFERRAL_FEE: u16 = 100;
+
+// Maximum volume-based fee tiers
+pub const MAX_FEE_TIERS: usize = 4;
+
+// Maximum creators listed in Metaplex metadata
+pub const MAX_METADATA_CREATORS: usize = 5;
+
+// Mint recorded for native SOL payments in events and seller stats
+pub const NATIVE_SOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+// Pyth pull oracle receiver program (owner of PriceUpdateV2 accounts)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+// Oracle prices older than this are rejected (seconds)
+pub const MAX_PRICE_AGE: i64 = 60;
+
+// Maximum oracle confidence interval relative to price (2%)
+pub const MAX_PRICE_CONFIDENCE_BPS: u64 = 200;
+
+// Decimals of USD-denominated auction parameters (micro-USD)
+pub const USD_DECIMALS: u32 = 6;
+
+// Metaplex Token Metadata program
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
 // Maximum bids per auction for gas optimization
 pub const MAX_BIDS_PER_AUCTION: usize = 1000;
 
+// Default refundable anti-spam deposit per bid (0.01 SOL)
+pub const DEFAULT_BID_DEPOSIT_LAMPORTS: u64 = 10_000_000;
+
+// Maximum anti-spam deposit per bid (1 SOL)
+pub const MAX_BID_DEPOSIT_LAMPORTS: u64 = 1_000_000_000;
+
+// Default cap on open bids per wallet
+pub const DEFAULT_MAX_ACTIVE_BIDS_PER_WALLET: u32 = 20;
+
+// Maximum orders per batch auction epoch (fills are recorded in one transaction)
+pub const MAX_ORDERS_PER_BATCH: u64 = 64;
+
+// Maximum distinct assets in a bundle lot
+pub const MAX_BUNDLE_ITEMS: usize = 8;
+
+// Maximum role grants held in the roles account
+pub const MAX_ROLE_GRANTS: usize = 16;
+
+// Maximum members of the admin multisig
+pub const MAX_MULTISIG_MEMBERS: usize = 10;
+
+// Maximum units offered in a multi-unit auction
+pub const MAX_UNITS_PER_AUCTION: u64 = 1_000_000;
+
+// Window for approving and executing an admin proposal (7 days in seconds)
+pub const PROPOSAL_LIFETIME: i64 = 7 * 24 * 60 * 60;
+
 // Authority transfer timelock (7 days in seconds)
 pub const AUTHORITY_TRANSFER_TIMELOCK: i64 = 7 * 24 * 60 * 60;
 
+// Default delay before queued fee changes take effect (2 days in seconds)
+pub const DEFAULT_FEE_UPDATE_DELAY: i64 = 2 * 24 * 60 * 60;
+
+// Bounds for the configurable fee update delay (1 to 30 days)
+pub const MIN_FEE_UPDATE_DELAY: i64 = 24 * 60 * 60;
+pub const MAX_FEE_UPDATE_DELAY: i64 = 30 * 24 * 60 * 60;
+
 // Minimum price floor for Dutch auctions (basis points, e.g., 100 = 1% of starting price)
 pub const MINIMUM_PRICE_FLOOR_BPS: u16 = 100;
\ No newline at end of file
@@ -1,13 +1,66 @@
-use anchor_lang::prelude::*;
-
 // Re-export from mod.rs for consistency
 pub use super::{
     ProtocolState,
+    ProtocolRoles,
+    RoleGrant,
+    Role,
+    FeeLedger,
+    SellerStats,
+    FeeTier,
+    AdminMultisig,
+    AdminProposal,
+    AdminAction,
     BatchSettlement,
     BatchStatus,
+    SingleAuctionResult,
     BatchSettlementCreated,
     BatchSettled,
+    BatchAuctionFailed,
+    RoleGranted,
+    RoleRevoked,
+    AuthorityTransferInitiated,
+    AuthorityTransferCompleted,
+    AuthorityTransferCancelled,
+    PauseFlagsUpdated,
+    FeeUpdateQueued,
+    FeeUpdateApplied,
+    FeeUpdateCancelled,
+    ProtocolFeeCollected,
+    FeesWithdrawn,
+    MultisigConfigured,
+    ProposalCreated,
+    ProposalApproved,
+    ProposalExecuted,
+    AccountMigrated,
+    BidLimitsUpdated,
     PROTOCOL_SEED,
+    PROTOCOL_STATE_VERSION,
+    BATCH_SETTLEMENT_VERSION,
     BATCH_SEED,
+    ROLES_SEED,
+    MAX_ROLE_GRANTS,
+    MULTISIG_SEED,
+    PROPOSAL_SEED,
+    TREASURY_SEED,
+    FEE_VAULT_SEED,
+    FEE_LEDGER_SEED,
+    SOL_FEE_VAULT_SEED,
+    NATIVE_SOL_MINT,
+    SELLER_STATS_SEED,
+    MAX_MULTISIG_MEMBERS,
     MAX_PROTOCOL_FEE,
+    MAX_REFERRAL_FEE,
+    MAX_FEE_TIERS,
+    PAUSE_CREATE,
+    PAUSE_BID,
+    PAUSE_SETTLE,
+    PAUSE_SEALED_BID,
+    PAUSE_DUTCH,
+    PAUSE_BATCH,
+    PAUSE_MULTI_UNIT,
+    PAUSE_REVERSE,
+    PAUSE_ALL,
+    DEFAULT_FEE_UPDATE_DELAY,
+    MIN_FEE_UPDATE_DELAY,
+    MAX_FEE_UPDATE_DELAY,
 };
\ No newline at end of file